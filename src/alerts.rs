@@ -0,0 +1,64 @@
+//! Module to hold the wrapper that reads the NWARN and NERR alert pins via GPIOs
+
+use embedded_hal::digital::InputPin;
+
+use crate::IcMd;
+use crate::configs::{DeviceStatus, ErrorStatus, WarningStatus};
+
+/// iC-MD driver with the NWARN and NERR outputs connected to GPIO inputs.
+///
+/// The NWARN and NERR outputs of the iC-MD are active low. If they are wired to inputs of your
+/// microcontroller, this wrapper allows to check for warnings and errors without polling the
+/// device via SPI. Both pins must have the same error type. The wrapped driver can be accessed
+/// via `icmd()`.
+#[derive(Debug)]
+pub struct IcMdWithAlerts<Spi, WarnPin, ErrPin> {
+    icmd: IcMd<Spi>,
+    warn_pin: WarnPin,
+    err_pin: ErrPin,
+}
+
+impl<Spi, WarnPin, ErrPin> IcMdWithAlerts<Spi, WarnPin, ErrPin>
+where
+    WarnPin: InputPin,
+    ErrPin: InputPin<Error = WarnPin::Error>,
+{
+    /// Create a new wrapper from a driver and the two input pins.
+    ///
+    /// # Arguments
+    /// * `icmd`: The iC-MD driver.
+    /// * `warn_pin`: The input pin that is connected to NWARN.
+    /// * `err_pin`: The input pin that is connected to NERR.
+    pub fn new(icmd: IcMd<Spi>, warn_pin: WarnPin, err_pin: ErrPin) -> Self {
+        Self {
+            icmd,
+            warn_pin,
+            err_pin,
+        }
+    }
+
+    /// Read the NWARN and NERR pins and return them as a device status.
+    /// A low pin level signals a warning or an error, respectively. No SPI communication takes
+    /// place and the cached device status of the driver is not changed.
+    pub fn alerts(&mut self) -> Result<DeviceStatus, WarnPin::Error> {
+        let warning = match self.warn_pin.is_low()? {
+            true => WarningStatus::Warning,
+            false => WarningStatus::Ok,
+        };
+        let error = match self.err_pin.is_low()? {
+            true => ErrorStatus::Error,
+            false => ErrorStatus::Ok,
+        };
+        Ok(DeviceStatus { warning, error })
+    }
+
+    /// Get a mutable reference to the wrapped driver.
+    pub fn icmd(&mut self) -> &mut IcMd<Spi> {
+        &mut self.icmd
+    }
+
+    /// Consume the wrapper and return the driver and the two input pins.
+    pub fn release(self) -> (IcMd<Spi>, WarnPin, ErrPin) {
+        (self.icmd, self.warn_pin, self.err_pin)
+    }
+}
@@ -0,0 +1,25 @@
+//! Module to create the iC-MD driver from a `SpiBus` with a manually driven chip select pin
+
+use embedded_hal::{digital::OutputPin, spi::SpiBus};
+use embedded_hal_bus::spi::{ExclusiveDevice, NoDelay};
+
+use crate::IcMd;
+
+impl<Bus: SpiBus, Cs: OutputPin> IcMd<ExclusiveDevice<Bus, Cs, NoDelay>> {
+    /// Creates a new instance of the iC-MD driver from a `SpiBus` and a chip select pin.
+    /// The bus and the pin are wrapped in an `ExclusiveDevice` of `embedded-hal-bus`, which
+    /// frames every transaction with the chip select pin and sets the pin high on creation.
+    /// Otherwise, this is the same as `new()`.
+    ///
+    /// The device is created without a delay provider. This is fine, since the driver never
+    /// issues delay operations within a transaction.
+    ///
+    /// Requires the `embedded-hal-bus` feature.
+    ///
+    /// # Arguments
+    /// * `bus`: The SPI bus to use.
+    /// * `cs`: The output pin that is connected to the NCS input of the iC-MD.
+    pub fn from_bus(bus: Bus, cs: Cs) -> Result<Self, Cs::Error> {
+        Ok(Self::new(ExclusiveDevice::new_no_delay(bus, cs)?))
+    }
+}
@@ -1,13 +1,21 @@
 //! Module to hold the configuration and status structs for the device
 
-use core::{convert::From, default::Default, fmt::Debug};
+use core::{
+    convert::From,
+    default::Default,
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+use crate::error::{ConfigError, CountError, InvalidRegisterValue, ParseError};
+use crate::motion::{RawCount, sign_extend, wrapped_delta};
 
 /// Represent the counter values for different configurations of the iC-MD quadrature counter.
 ///
 /// If more than one counter value is present, the counter values are always in the order of
 /// Counter 0, Counter 1, and Counter 2.
 /// Note: The size of the returned value depends on the configuration of the counter!
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum CntCount {
     /// Counter return value for configuration counter 0 = 24 bit; 1 counter; TTL, RS422, or LVDS
@@ -21,7 +29,7 @@ pub enum CntCount {
     /// Counter return value for configuration counter 0 = 32 bit; 1 counter; TTL, RS422, or LVDS
     Cnt1Bit32(i32),
     /// Counter return value for configuration counter 0 = 32 bit and Counter 1 = 16 bit; 2 counters; TTL only
-    Cnt2Bit32Bit16(i16, i32),
+    Cnt2Bit32Bit16(i32, i16),
     /// Counter return value for configuration counter 0 = 16 bit and Counter 1 = 16 bit; 2 counters; TTL only
     Cnt2Bit16(i16, i16),
     /// Counter return value for configuration counter 0 = 16 bit, Counter 1 = 16 bit, and Counter 2 = 16 bit;
@@ -32,21 +40,29 @@ pub enum CntCount {
 impl CntCount {
     /// Get the value of the counter zero
     ///
-    /// If it exists, this will return `Some(value)`. Otherwise it will return `None`. For counter
-    /// zero, this will always exist, as it is always configured.
-    pub fn get_cnt0(&self) -> Option<i64> {
+    /// Counter zero is always configured, so its value always exists. Use this instead of
+    /// `get_cnt0()` to avoid unwrapping the value.
+    pub fn cnt0(&self) -> i64 {
         match self {
-            CntCount::Cnt1Bit24(val) => Some(*val as i64),
-            CntCount::Cnt2Bit24(val, _) => Some(*val as i64),
-            CntCount::Cnt1Bit48(val) => Some(*val),
-            CntCount::Cnt1Bit16(val) => Some(*val as i64),
-            CntCount::Cnt1Bit32(val) => Some(*val as i64),
-            CntCount::Cnt2Bit32Bit16(val, _) => Some(*val as i64),
-            CntCount::Cnt2Bit16(val, _) => Some(*val as i64),
-            CntCount::Cnt3Bit16(val, _, _) => Some(*val as i64),
+            CntCount::Cnt1Bit24(val) => *val as i64,
+            CntCount::Cnt2Bit24(val, _) => *val as i64,
+            CntCount::Cnt1Bit48(val) => *val,
+            CntCount::Cnt1Bit16(val) => *val as i64,
+            CntCount::Cnt1Bit32(val) => *val as i64,
+            CntCount::Cnt2Bit32Bit16(val, _) => *val as i64,
+            CntCount::Cnt2Bit16(val, _) => *val as i64,
+            CntCount::Cnt3Bit16(val, _, _) => *val as i64,
         }
     }
 
+    /// Get the value of the counter zero
+    ///
+    /// If it exists, this will return `Some(value)`. Otherwise it will return `None`. For counter
+    /// zero, this will always exist, as it is always configured. See also `cnt0()`.
+    pub fn get_cnt0(&self) -> Option<i64> {
+        Some(self.cnt0())
+    }
+
     /// Get the value of the counter one
     ///
     /// If it exists, this will return `Some(value)`. Otherwise it will return `None`.
@@ -69,6 +85,131 @@ impl CntCount {
             _ => None,
         }
     }
+
+    /// Get the value of the given counter channel.
+    ///
+    /// Contrary to the `get_cntN()` getters, the error distinguishes a channel index that is out
+    /// of range (0 to 2) from a channel that is not configured, e.g., for error messages in
+    /// generic code.
+    ///
+    /// # Arguments
+    /// * `channel`: The counter channel (0, 1, or 2).
+    pub fn get(&self, channel: u8) -> Result<i64, CountError> {
+        if channel > 2 {
+            return Err(CountError::OutOfRange(channel));
+        }
+        self.get_channel(channel)
+            .ok_or(CountError::NotConfigured(channel))
+    }
+
+    /// Get the value of the counter zero as unsigned value.
+    ///
+    /// The two's complement bits of the counter are reinterpreted as unsigned value of the
+    /// configured bit depth, i.e., without sign extension. This is useful if you treat the
+    /// counter as a ring and handle the wrap-around yourself. Counter zero always exists.
+    pub fn get_cnt0_unsigned(&self) -> Option<u64> {
+        self.get_channel_unsigned(0)
+    }
+
+    /// Get the value of the counter one as unsigned value.
+    ///
+    /// See `get_cnt0_unsigned()`. If counter one does not exist, `None` is returned.
+    pub fn get_cnt1_unsigned(&self) -> Option<u64> {
+        self.get_channel_unsigned(1)
+    }
+
+    /// Get the value of the counter two as unsigned value.
+    ///
+    /// See `get_cnt0_unsigned()`. If counter two does not exist, `None` is returned.
+    pub fn get_cnt2_unsigned(&self) -> Option<u64> {
+        self.get_channel_unsigned(2)
+    }
+
+    /// Get the values of all counters as an array.
+    ///
+    /// The array is in channel order, i.e., `[cnt0, cnt1, cnt2]`. Configured counters are
+    /// `Some(value)`, counters that are not configured are `None`.
+    pub fn as_array(&self) -> [Option<i64>; 3] {
+        [self.get_cnt0(), self.get_cnt1(), self.get_cnt2()]
+    }
+
+    /// Compare the configured counter values with the given values.
+    ///
+    /// Returns `true` if the number of configured counters matches the length of `values` and
+    /// all counter values are equal to the given ones in channel order, `false` otherwise.
+    pub fn matches(&self, values: &[i64]) -> bool {
+        let mut configured = self.as_array().into_iter().flatten();
+        values.iter().all(|val| configured.next() == Some(*val)) && configured.next().is_none()
+    }
+
+    /// Get the value of the given counter channel as a `RawCount`.
+    ///
+    /// If the channel (0, 1, or 2) exists, this will return `Some(value)`. Otherwise it will
+    /// return `None`.
+    pub fn raw_count(&self, channel: u8) -> Option<RawCount> {
+        self.get_channel(channel).map(RawCount::new)
+    }
+
+    /// Compute the signed difference of a counter channel since a previous read.
+    ///
+    /// The counters wrap around when they overflow. The difference is therefore corrected for
+    /// the wrap-around using the bit depth of the counter, assuming that the counter moved less
+    /// than half its range in between the two reads.
+    ///
+    /// Returns `None` if the channel does not exist or if `prev` stems from a different counter
+    /// configuration.
+    ///
+    /// # Arguments
+    /// * `prev`: The previous counter read.
+    /// * `channel`: The counter channel (0, 1, or 2).
+    pub fn delta_from(&self, prev: &CntCount, channel: u8) -> Option<i64> {
+        if core::mem::discriminant(self) != core::mem::discriminant(prev) {
+            return None;
+        }
+        let bits = self.channel_bits(channel)?;
+        Some(wrapped_delta(
+            prev.get_channel(channel)?,
+            self.get_channel(channel)?,
+            bits,
+        ))
+    }
+
+    /// Get the value of the given counter channel, `None` if it does not exist.
+    pub(crate) fn get_channel(&self, channel: u8) -> Option<i64> {
+        match channel {
+            0 => self.get_cnt0(),
+            1 => self.get_cnt1(),
+            2 => self.get_cnt2(),
+            _ => None,
+        }
+    }
+
+    /// Get the value of the given counter channel masked to its bit depth, `None` if it does not
+    /// exist.
+    fn get_channel_unsigned(&self, channel: u8) -> Option<u64> {
+        let bits = self.channel_bits(channel)?;
+        Some(self.get_channel(channel)? as u64 & (u64::MAX >> (64 - bits)))
+    }
+
+    /// Get the bit depth of the given counter channel, `None` if it does not exist.
+    pub(crate) fn channel_bits(&self, channel: u8) -> Option<u32> {
+        self.layout().channel_bits(channel)
+    }
+
+    /// Get the counter configuration that produces this counter value, with default setups.
+    fn layout(&self) -> CntCfg {
+        let setup = CntSetup::default();
+        match self {
+            CntCount::Cnt1Bit24(_) => CntCfg::Cnt1Bit24(setup),
+            CntCount::Cnt2Bit24(_, _) => CntCfg::Cnt2Bit24(setup, setup),
+            CntCount::Cnt1Bit48(_) => CntCfg::Cnt1Bit48(setup),
+            CntCount::Cnt1Bit16(_) => CntCfg::Cnt1Bit16(setup),
+            CntCount::Cnt1Bit32(_) => CntCfg::Cnt1Bit32(setup),
+            CntCount::Cnt2Bit32Bit16(_, _) => CntCfg::Cnt2Bit32Bit16(setup, setup),
+            CntCount::Cnt2Bit16(_, _) => CntCfg::Cnt2Bit16(setup, setup),
+            CntCount::Cnt3Bit16(_, _, _) => CntCfg::Cnt3Bit16(setup, setup, setup),
+        }
+    }
 }
 
 /// Enum to specify the direction in which a counter counts
@@ -94,6 +235,32 @@ impl From<CntDirection> for u8 {
     }
 }
 
+impl Display for CntDirection {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CntDirection::CW => write!(f, "cw"),
+            CntDirection::CCW => write!(f, "ccw"),
+        }
+    }
+}
+
+impl FromStr for CntDirection {
+    type Err = ParseError;
+
+    /// Parse a counting direction from `"cw"` or `"ccw"`, ignoring the case and surrounding
+    /// whitespace.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("cw") {
+            Ok(CntDirection::CW)
+        } else if s.eq_ignore_ascii_case("ccw") {
+            Ok(CntDirection::CCW)
+        } else {
+            Err(ParseError::UnknownDirection)
+        }
+    }
+}
+
 /// Enum to specify if the Z signal is normal or inverted
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -114,6 +281,32 @@ impl From<CntZSignal> for u8 {
     }
 }
 
+impl Display for CntZSignal {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CntZSignal::Normal => write!(f, "normal"),
+            CntZSignal::Inverted => write!(f, "inverted"),
+        }
+    }
+}
+
+impl FromStr for CntZSignal {
+    type Err = ParseError;
+
+    /// Parse a Z signal setup from `"normal"` or `"inverted"`, ignoring the case and
+    /// surrounding whitespace.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("normal") {
+            Ok(CntZSignal::Normal)
+        } else if s.eq_ignore_ascii_case("inverted") {
+            Ok(CntZSignal::Inverted)
+        } else {
+            Err(ParseError::UnknownZSignal)
+        }
+    }
+}
+
 /// Setup for a specific counter.
 ///
 /// Use this struct to declare the setup of a specific counter.
@@ -126,12 +319,32 @@ pub struct CntSetup {
 
 impl CntSetup {
     /// Create a new counter setup with the given direction and Z signal.
-    pub fn new(count_direction: CntDirection, z_signal: CntZSignal) -> Self {
+    pub const fn new(count_direction: CntDirection, z_signal: CntZSignal) -> Self {
         Self {
             count_direction,
             z_signal,
         }
     }
+
+    /// Get the counting direction of this counter setup.
+    pub fn count_direction(&self) -> CntDirection {
+        self.count_direction
+    }
+
+    /// Set the counting direction of this counter setup.
+    pub fn set_count_direction(&mut self, count_direction: CntDirection) {
+        self.count_direction = count_direction;
+    }
+
+    /// Get the Z signal setup of this counter setup.
+    pub fn z_signal(&self) -> CntZSignal {
+        self.z_signal
+    }
+
+    /// Set the Z signal setup of this counter setup.
+    pub fn set_z_signal(&mut self, z_signal: CntZSignal) {
+        self.z_signal = z_signal;
+    }
 }
 
 /// Counter configuration
@@ -166,6 +379,283 @@ pub enum CntCfg {
     Cnt3Bit16(CntSetup, CntSetup, CntSetup),
 }
 
+/// Counter frame
+///
+/// Raw bytes of a counter read as they were sent by the device, including the status bits NERR
+/// and NWARN in the last byte. If the byte order is overridden with
+/// `IcMd::set_byte_order_override()`, the bytes are already flipped back into this order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CounterFrame {
+    bytes: [u8; 7],
+    len: usize,
+}
+
+impl CounterFrame {
+    /// Create a new, zeroed frame with the given length of at most 7 bytes.
+    pub(crate) const fn new(len: usize) -> Self {
+        Self { bytes: [0; 7], len }
+    }
+
+    /// Get the raw bytes of the frame.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+
+    /// Get the raw bytes of the frame mutably, e.g., to read them from the device.
+    pub(crate) fn as_bytes_mut(&mut self) -> &mut [u8] {
+        &mut self.bytes[..self.len]
+    }
+
+    /// Get the status byte of the frame, i.e., its last byte.
+    pub(crate) fn status(&self) -> u8 {
+        self.bytes[self.len - 1]
+    }
+}
+
+/// Counter read register
+///
+/// Enum of the `ReadCntCfgN` registers in the device driver that are used to read the counter
+/// values of the different counter configurations. See `CntCfg::count_register()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CntRegister {
+    /// `ReadCntCfg0`: One 24 bit counter
+    ReadCntCfg0,
+    /// `ReadCntCfg1`: Two 24 bit counters
+    ReadCntCfg1,
+    /// `ReadCntCfg2`: One 48 bit counter
+    ReadCntCfg2,
+    /// `ReadCntCfg3`: One 16 bit counter
+    ReadCntCfg3,
+    /// `ReadCntCfg4`: One 32 bit counter
+    ReadCntCfg4,
+    /// `ReadCntCfg5`: One 32 bit and one 16 bit counter
+    ReadCntCfg5,
+    /// `ReadCntCfg6`: Two 16 bit counters
+    ReadCntCfg6,
+    /// `ReadCntCfg7`: Three 16 bit counters
+    ReadCntCfg7,
+}
+
+impl CntCfg {
+    /// Get the register of the device driver that is used to read the counter values.
+    ///
+    /// This is the only place where counter configurations are mapped to registers.
+    pub const fn count_register(&self) -> CntRegister {
+        match self {
+            CntCfg::Cnt1Bit24(_) => CntRegister::ReadCntCfg0,
+            CntCfg::Cnt2Bit24(_, _) => CntRegister::ReadCntCfg1,
+            CntCfg::Cnt1Bit48(_) => CntRegister::ReadCntCfg2,
+            CntCfg::Cnt1Bit16(_) => CntRegister::ReadCntCfg3,
+            CntCfg::Cnt1Bit32(_) => CntRegister::ReadCntCfg4,
+            CntCfg::Cnt2Bit32Bit16(_, _) => CntRegister::ReadCntCfg5,
+            CntCfg::Cnt2Bit16(_, _) => CntRegister::ReadCntCfg6,
+            CntCfg::Cnt3Bit16(_, _, _) => CntRegister::ReadCntCfg7,
+        }
+    }
+
+    /// Get the bit depth of the given counter channel, `None` if it is not configured.
+    pub(crate) const fn channel_bits(&self, channel: u8) -> Option<u32> {
+        match (self, channel) {
+            (CntCfg::Cnt1Bit24(_), 0) | (CntCfg::Cnt2Bit24(_, _), 0 | 1) => Some(24),
+            (CntCfg::Cnt1Bit48(_), 0) => Some(48),
+            (CntCfg::Cnt1Bit16(_), 0)
+            | (CntCfg::Cnt2Bit32Bit16(_, _), 1)
+            | (CntCfg::Cnt2Bit16(_, _), 0 | 1)
+            | (CntCfg::Cnt3Bit16(_, _, _), 0..=2) => Some(16),
+            (CntCfg::Cnt1Bit32(_), 0) | (CntCfg::Cnt2Bit32Bit16(_, _), 0) => Some(32),
+            _ => None,
+        }
+    }
+
+    /// Check that all settings of this configuration take effect on the device.
+    ///
+    /// In the three counter configuration, no Z signals can be connected and the Z signal
+    /// setup of each counter is ignored. If any counter specifies a non-default Z signal in this
+    /// configuration, an error with the first affected channel is returned.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if let CntCfg::Cnt3Bit16(i, j, k) = self {
+            for (channel, setup) in [i, j, k].into_iter().enumerate() {
+                if setup.z_signal != CntZSignal::default() {
+                    return Err(ConfigError::ZSignalIgnored(channel as u8));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the bit width of each counter channel in channel order.
+    ///
+    /// Configured channels report their bit width (16, 24, 32, or 48), channels that are not
+    /// configured are `None`. This can be used, e.g., to size buffers or to choose integer types.
+    pub const fn channel_widths(&self) -> [Option<u8>; 3] {
+        let mut widths = [None; 3];
+        let mut channel = 0;
+        while channel < 3 {
+            if let Some(bits) = self.channel_bits(channel as u8) {
+                widths[channel] = Some(bits as u8);
+            }
+            channel += 1;
+        }
+        widths
+    }
+
+    /// Get the bit depth of the widest configured counter, e.g., to choose an integer type that
+    /// is big enough for all channels.
+    pub const fn max_bit_depth(&self) -> u8 {
+        match self {
+            CntCfg::Cnt1Bit16(_) | CntCfg::Cnt2Bit16(_, _) | CntCfg::Cnt3Bit16(_, _, _) => 16,
+            CntCfg::Cnt1Bit24(_) | CntCfg::Cnt2Bit24(_, _) => 24,
+            CntCfg::Cnt1Bit32(_) | CntCfg::Cnt2Bit32Bit16(_, _) => 32,
+            CntCfg::Cnt1Bit48(_) => 48,
+        }
+    }
+
+    /// Get the number of configured counters (1, 2, or 3).
+    pub const fn num_counters(&self) -> usize {
+        match self {
+            CntCfg::Cnt1Bit24(_)
+            | CntCfg::Cnt1Bit48(_)
+            | CntCfg::Cnt1Bit16(_)
+            | CntCfg::Cnt1Bit32(_) => 1,
+            CntCfg::Cnt2Bit24(_, _) | CntCfg::Cnt2Bit32Bit16(_, _) | CntCfg::Cnt2Bit16(_, _) => 2,
+            CntCfg::Cnt3Bit16(_, _, _) => 3,
+        }
+    }
+
+    /// Check if the given counter channel (0, 1, or 2) is configured, i.e., if the corresponding
+    /// getter of `CntCount` returns `Some` for reads with this configuration.
+    pub const fn has_channel(&self, channel: u8) -> bool {
+        self.channel_bits(channel).is_some()
+    }
+
+    /// Get a reference to the setup of the given counter channel.
+    ///
+    /// Returns `None` if the channel is not configured in this configuration.
+    pub fn setup(&self, channel: u8) -> Option<&CntSetup> {
+        match (self, channel) {
+            (CntCfg::Cnt1Bit24(i), 0)
+            | (CntCfg::Cnt1Bit48(i), 0)
+            | (CntCfg::Cnt1Bit16(i), 0)
+            | (CntCfg::Cnt1Bit32(i), 0) => Some(i),
+            (CntCfg::Cnt2Bit24(i, _), 0)
+            | (CntCfg::Cnt2Bit32Bit16(i, _), 0)
+            | (CntCfg::Cnt2Bit16(i, _), 0)
+            | (CntCfg::Cnt3Bit16(i, _, _), 0) => Some(i),
+            (CntCfg::Cnt2Bit24(_, j), 1)
+            | (CntCfg::Cnt2Bit32Bit16(_, j), 1)
+            | (CntCfg::Cnt2Bit16(_, j), 1)
+            | (CntCfg::Cnt3Bit16(_, j, _), 1) => Some(j),
+            (CntCfg::Cnt3Bit16(_, _, k), 2) => Some(k),
+            _ => None,
+        }
+    }
+
+    /// Get a mutable reference to the setup of the given counter channel.
+    ///
+    /// Returns `None` if the channel is not configured in this configuration.
+    pub fn setup_mut(&mut self, channel: u8) -> Option<&mut CntSetup> {
+        match (self, channel) {
+            (CntCfg::Cnt1Bit24(i), 0)
+            | (CntCfg::Cnt1Bit48(i), 0)
+            | (CntCfg::Cnt1Bit16(i), 0)
+            | (CntCfg::Cnt1Bit32(i), 0) => Some(i),
+            (CntCfg::Cnt2Bit24(i, _), 0)
+            | (CntCfg::Cnt2Bit32Bit16(i, _), 0)
+            | (CntCfg::Cnt2Bit16(i, _), 0)
+            | (CntCfg::Cnt3Bit16(i, _, _), 0) => Some(i),
+            (CntCfg::Cnt2Bit24(_, j), 1)
+            | (CntCfg::Cnt2Bit32Bit16(_, j), 1)
+            | (CntCfg::Cnt2Bit16(_, j), 1)
+            | (CntCfg::Cnt3Bit16(_, j, _), 1) => Some(j),
+            (CntCfg::Cnt3Bit16(_, _, k), 2) => Some(k),
+            _ => None,
+        }
+    }
+
+    /// Decode the raw bytes of a counter read with this configuration.
+    ///
+    /// The bytes are in big-endian order: The counter values start at bit 8 with counter 0,
+    /// followed by the other counters. The lowest byte holds the NERR (bit 7) and NWARN (bit 6)
+    /// status bits, which are not decoded here.
+    pub(crate) fn decode(&self, raw: &[u8]) -> CntCount {
+        let counter = |channel: u8| self.decode_channel(raw, channel).unwrap_or(0);
+        match self {
+            CntCfg::Cnt1Bit24(_) => CntCount::Cnt1Bit24(counter(0) as i32),
+            CntCfg::Cnt2Bit24(_, _) => CntCount::Cnt2Bit24(counter(0) as i32, counter(1) as i32),
+            CntCfg::Cnt1Bit48(_) => CntCount::Cnt1Bit48(counter(0)),
+            CntCfg::Cnt1Bit16(_) => CntCount::Cnt1Bit16(counter(0) as i16),
+            CntCfg::Cnt1Bit32(_) => CntCount::Cnt1Bit32(counter(0) as i32),
+            CntCfg::Cnt2Bit32Bit16(_, _) => {
+                CntCount::Cnt2Bit32Bit16(counter(0) as i32, counter(1) as i16)
+            }
+            CntCfg::Cnt2Bit16(_, _) => CntCount::Cnt2Bit16(counter(0) as i16, counter(1) as i16),
+            CntCfg::Cnt3Bit16(_, _, _) => {
+                CntCount::Cnt3Bit16(counter(0) as i16, counter(1) as i16, counter(2) as i16)
+            }
+        }
+    }
+
+    /// Decode the value of the given counter channel from the raw bytes of a counter read, see
+    /// `decode()`. Returns `None` if the channel is not configured.
+    pub(crate) fn decode_channel(&self, raw: &[u8], channel: u8) -> Option<i64> {
+        let bits = self.channel_bits(channel)?;
+        let value = raw.iter().fold(0u64, |acc, byte| (acc << 8) | *byte as u64);
+        let mut shift = 8;
+        for ch in 0..channel {
+            shift += self.channel_bits(ch).unwrap_or(0);
+        }
+        Some(sign_extend((value >> shift) as i64, bits))
+    }
+
+    /// Get the number of bytes that are read from the device to read the counter values.
+    ///
+    /// This includes the two status bits (NERR and NWARN) that are sent along with the counter
+    /// values and corresponds to the size of the `ReadCntCfgN` registers in the device driver.
+    pub const fn read_len(&self) -> usize {
+        match self {
+            CntCfg::Cnt1Bit24(_) => 4,
+            CntCfg::Cnt2Bit24(_, _) => 7,
+            CntCfg::Cnt1Bit48(_) => 7,
+            CntCfg::Cnt1Bit16(_) => 3,
+            CntCfg::Cnt1Bit32(_) => 5,
+            CntCfg::Cnt2Bit32Bit16(_, _) => 7,
+            CntCfg::Cnt2Bit16(_, _) => 5,
+            CntCfg::Cnt3Bit16(_, _, _) => 7,
+        }
+    }
+}
+
+// Compile-time check that the read length of each configuration matches the bit depths of its
+// counters plus the two status bits.
+const _: () = {
+    const SETUP: CntSetup = CntSetup::new(CntDirection::CW, CntZSignal::Normal);
+    let configs = [
+        CntCfg::Cnt1Bit24(SETUP),
+        CntCfg::Cnt2Bit24(SETUP, SETUP),
+        CntCfg::Cnt1Bit48(SETUP),
+        CntCfg::Cnt1Bit16(SETUP),
+        CntCfg::Cnt1Bit32(SETUP),
+        CntCfg::Cnt2Bit32Bit16(SETUP, SETUP),
+        CntCfg::Cnt2Bit16(SETUP, SETUP),
+        CntCfg::Cnt3Bit16(SETUP, SETUP, SETUP),
+    ];
+    let mut i = 0;
+    while i < configs.len() {
+        let mut bits = 2;
+        let mut channel = 0;
+        while channel < 3 {
+            if let Some(channel_bits) = configs[i].channel_bits(channel) {
+                bits += channel_bits;
+            }
+            channel += 1;
+        }
+        assert!(configs[i].read_len() == bits.div_ceil(8) as usize);
+        i += 1;
+    }
+};
+
 impl From<CntCfg> for u8 {
     fn from(val: CntCfg) -> Self {
         match val {
@@ -220,6 +710,305 @@ impl From<CntCfg> for u8 {
     }
 }
 
+impl From<u8> for CntCfg {
+    /// Convert the raw register byte into a counter configuration. Bits that are not used by
+    /// the selected configuration are ignored.
+    fn from(val: u8) -> Self {
+        let direction = |bit: u8| match (val >> bit) & 0b1 {
+            0 => CntDirection::CW,
+            _ => CntDirection::CCW,
+        };
+        let z_signal = |bit: u8| match (val >> bit) & 0b1 {
+            0 => CntZSignal::Normal,
+            _ => CntZSignal::Inverted,
+        };
+        let i = CntSetup::new(direction(3), z_signal(6));
+        let j = CntSetup::new(direction(4), z_signal(7));
+        match val & 0b111 {
+            0b000 => CntCfg::Cnt1Bit24(i),
+            0b001 => CntCfg::Cnt2Bit24(i, j),
+            0b010 => CntCfg::Cnt1Bit48(i),
+            0b011 => CntCfg::Cnt1Bit16(i),
+            0b100 => CntCfg::Cnt1Bit32(i),
+            0b101 => CntCfg::Cnt2Bit32Bit16(i, j),
+            0b110 => CntCfg::Cnt2Bit16(i, j),
+            _ => CntCfg::Cnt3Bit16(
+                CntSetup::new(direction(3), CntZSignal::Normal),
+                CntSetup::new(direction(4), CntZSignal::Normal),
+                CntSetup::new(direction(5), CntZSignal::Normal),
+            ),
+        }
+    }
+}
+
+impl FromStr for CntCfg {
+    type Err = ParseError;
+
+    /// Parse a counter configuration from its datasheet-style name, e.g., `"2x24"` for two 24-bit
+    /// counters. The mixed configuration with a 32-bit and a 16-bit counter is named `"32+16"`.
+    /// Surrounding whitespace is ignored, the `x` may also be uppercase. All counters are set up
+    /// with the default counting direction and Z signal.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let names = [
+            "1x24", "2x24", "1x48", "1x16", "1x32", "32+16", "2x16", "3x16",
+        ];
+        let cfg = names
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(s.trim()))
+            .ok_or(ParseError::UnknownCounterConfig)?;
+        // The names are in the order of the configuration bits, the setup bits are all zero.
+        Ok(CntCfg::from(cfg as u8))
+    }
+}
+
+/// Configuration snapshot
+///
+/// Raw bytes of the configuration registers as read back from the device, e.g., to capture the
+/// state of a device for diagnostics and to reapply it later with `IcMd::restore_config()`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ConfigSnapshot {
+    /// Counter configuration register (0x00)
+    pub counter_config: u8,
+    /// Operation mode register (0x01)
+    pub operation_mode: u8,
+}
+
+/// Counter configuration profiles
+///
+/// Stores a fixed number of counter configurations that can be applied to the device by their
+/// index, e.g., to switch between different measurement modes at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ConfigProfiles<const N: usize> {
+    profiles: [CntCfg; N],
+}
+
+impl<const N: usize> ConfigProfiles<N> {
+    /// Create new configuration profiles from the given counter configurations.
+    pub const fn new(profiles: [CntCfg; N]) -> Self {
+        Self { profiles }
+    }
+
+    /// Get the counter configuration of the profile with the given index.
+    ///
+    /// Returns `None` if the index is out of range.
+    pub fn get(&self, index: usize) -> Option<CntCfg> {
+        self.profiles.get(index).copied()
+    }
+
+    /// Get the number of stored profiles.
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Return `true` if no profiles are stored.
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+}
+
+/// Input type of the AB and Z inputs
+///
+/// The inputs can either be differential (RS-422 or LVDS) or TTL. Note that configurations with
+/// more than one counter only support TTL inputs, see the datasheet for more information.
+/// `IcMd::init()` rejects differential inputs with these configurations.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum InputType {
+    #[default]
+    /// Differential inputs (RS-422 or LVDS)
+    Differential,
+    /// TTL inputs
+    Ttl,
+}
+
+impl From<InputType> for u8 {
+    fn from(val: InputType) -> Self {
+        match val {
+            InputType::Differential => 0,
+            InputType::Ttl => 1,
+        }
+    }
+}
+
+/// Z signal mode
+///
+/// Selects the state of the A and B inputs in which the Z (index) signal is evaluated, i.e.,
+/// the quadrant of the AB period that has to coincide with the Z signal. This is the Z signal
+/// configuration in bits 3 and 4 of the operation mode register, see the datasheet for more
+/// information.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ZMode {
+    /// Z is evaluated while A and B are high
+    #[default]
+    AHighBHigh = 0b00,
+    /// Z is evaluated while A is high and B is low
+    AHighBLow = 0b01,
+    /// Z is evaluated while A is low and B is high
+    ALowBHigh = 0b10,
+    /// Z is evaluated while A and B are low
+    ALowBLow = 0b11,
+}
+
+impl From<ZMode> for u8 {
+    fn from(val: ZMode) -> Self {
+        val as u8
+    }
+}
+
+impl From<u8> for ZMode {
+    /// Convert the 2-bit Z signal configuration into a Z signal mode. Higher bits are ignored.
+    fn from(val: u8) -> Self {
+        match val & 0b11 {
+            0b00 => ZMode::AHighBHigh,
+            0b01 => ZMode::AHighBLow,
+            0b10 => ZMode::ALowBHigh,
+            _ => ZMode::ALowBLow,
+        }
+    }
+}
+
+/// Operation mode
+///
+/// Typed representation of the operation mode register at address 0x01. It contains the input
+/// type (bit 7), whether the Z signal clears counter 1 (bit 6) and counter 0 (bit 5), the Z signal
+/// configuration (bits 3 and 4, see `ZMode`), and the touch probe configuration (bits 1 and 2).
+/// Bit 0 is reserved and always zero.
+///
+/// Use the builder-style setters to create the operation mode, e.g.:
+///
+/// ```rust
+/// # use ic_md::{InputType, OperationMode};
+/// let mode = OperationMode::new()
+///     .with_input_type(InputType::Ttl)
+///     .with_z_clears_cnt0(true);
+///
+/// assert_eq!(u8::from(mode), 0b1010_0000);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OperationMode {
+    input_type: InputType,
+    z_clears_cnt0: bool,
+    z_clears_cnt1: bool,
+    z_config: u8,
+    tp_config: u8,
+}
+
+impl OperationMode {
+    /// Create a new operation mode with all settings at their default (power-on) values.
+    pub const fn new() -> Self {
+        Self {
+            input_type: InputType::Differential,
+            z_clears_cnt0: false,
+            z_clears_cnt1: false,
+            z_config: 0,
+            tp_config: 0,
+        }
+    }
+
+    /// Set the input type.
+    pub const fn with_input_type(mut self, input_type: InputType) -> Self {
+        self.input_type = input_type;
+        self
+    }
+
+    /// Set if the Z signal clears counter 0.
+    pub const fn with_z_clears_cnt0(mut self, enabled: bool) -> Self {
+        self.z_clears_cnt0 = enabled;
+        self
+    }
+
+    /// Set if the Z signal clears counter 1.
+    pub const fn with_z_clears_cnt1(mut self, enabled: bool) -> Self {
+        self.z_clears_cnt1 = enabled;
+        self
+    }
+
+    /// Set the 2-bit Z signal configuration, see datasheet for the meaning of the values.
+    /// Only the two lowest bits of `z_config` are used.
+    pub const fn with_z_config(mut self, z_config: u8) -> Self {
+        self.z_config = z_config & 0b11;
+        self
+    }
+
+    /// Set the Z signal mode. This is the typed variant of `with_z_config()`.
+    pub const fn with_z_mode(mut self, z_mode: ZMode) -> Self {
+        self.z_config = z_mode as u8;
+        self
+    }
+
+    /// Set the 2-bit touch probe configuration, see datasheet for the meaning of the values.
+    /// Only the two lowest bits of `tp_config` are used.
+    pub const fn with_tp_config(mut self, tp_config: u8) -> Self {
+        self.tp_config = tp_config & 0b11;
+        self
+    }
+
+    /// Get the input type.
+    pub fn input_type(&self) -> InputType {
+        self.input_type
+    }
+
+    /// Get if the Z signal clears counter 0.
+    pub fn z_clears_cnt0(&self) -> bool {
+        self.z_clears_cnt0
+    }
+
+    /// Get if the Z signal clears counter 1.
+    pub fn z_clears_cnt1(&self) -> bool {
+        self.z_clears_cnt1
+    }
+
+    /// Get the 2-bit Z signal configuration.
+    pub fn z_config(&self) -> u8 {
+        self.z_config
+    }
+
+    /// Get the Z signal mode.
+    pub fn z_mode(&self) -> ZMode {
+        ZMode::from(self.z_config)
+    }
+
+    /// Get the 2-bit touch probe configuration.
+    pub fn tp_config(&self) -> u8 {
+        self.tp_config
+    }
+}
+
+impl From<OperationMode> for u8 {
+    fn from(val: OperationMode) -> Self {
+        (u8::from(val.input_type) << 7)
+            | ((val.z_clears_cnt1 as u8) << 6)
+            | ((val.z_clears_cnt0 as u8) << 5)
+            | (val.z_config << 3)
+            | (val.tp_config << 1)
+    }
+}
+
+impl TryFrom<u8> for OperationMode {
+    type Error = InvalidRegisterValue;
+
+    /// Convert the raw register byte into an operation mode. Fails if the reserved bit 0 is set.
+    fn try_from(val: u8) -> Result<Self, Self::Error> {
+        if val & 0b1 != 0 {
+            return Err(InvalidRegisterValue(val));
+        }
+        let input_type = match val >> 7 {
+            0 => InputType::Differential,
+            _ => InputType::Ttl,
+        };
+        Ok(Self::new()
+            .with_input_type(input_type)
+            .with_z_clears_cnt1(val & (1 << 6) != 0)
+            .with_z_clears_cnt0(val & (1 << 5) != 0)
+            .with_z_config(val >> 3)
+            .with_tp_config(val >> 1))
+    }
+}
+
 /// Device Status
 ///
 /// This struct describes the status of the device. The variables that indicate if a warning or
@@ -236,6 +1025,35 @@ pub struct DeviceStatus {
 }
 
 impl DeviceStatus {
+    /// Create a new device status without errors or warnings, same as the default.
+    pub(crate) const fn new() -> Self {
+        Self {
+            warning: WarningStatus::Ok,
+            error: ErrorStatus::Ok,
+        }
+    }
+
+    /// Create the device status from the NWARN and NERR bits of a counter read.
+    /// Both bits are active low, i.e., `true` means that there is no warning or error.
+    pub(crate) const fn from_bits(nwarn: bool, nerr: bool) -> Self {
+        Self {
+            warning: match nwarn {
+                true => WarningStatus::Ok,
+                false => WarningStatus::Warning,
+            },
+            error: match nerr {
+                true => ErrorStatus::Ok,
+                false => ErrorStatus::Error,
+            },
+        }
+    }
+
+    /// Create the device status from the status byte of a counter read, see
+    /// `CounterFrame::status()`. NERR is located at bit 7 and NWARN at bit 6.
+    pub(crate) const fn from_frame_status(status: u8) -> Self {
+        Self::from_bits(status & 0x40 != 0, status & 0x80 != 0)
+    }
+
     /// Return `true` if the device has no errors or warnings, false otherwise.
     pub fn is_ok(&self) -> bool {
         self.warning == WarningStatus::Ok && self.error == ErrorStatus::Ok
@@ -303,17 +1121,374 @@ pub struct FullDeviceStatus {
     pub ssi_enabled: InterfaceStatus,
 }
 
+impl FullDeviceStatus {
+    /// Decode the raw bytes of `Status0`, `Status1`, and `Status2` (in this order), with the
+    /// same mapping as `IcMd::get_full_device_status()`.
+    pub(crate) fn from_raw(raw: [u8; 3]) -> Self {
+        let bit = |byte: usize, bit: u8| raw[byte] & (1 << bit) != 0;
+        FullDeviceStatus {
+            cnt0_overflow: bit(0, 6).into(),
+            cnt0_aberr: bit(0, 7).into(),
+            cnt0_zero: bit(0, 5).into(),
+            cnt1_overflow: bit(1, 6).into(),
+            cnt1_aberr: bit(1, 7).into(),
+            cnt1_zero: bit(1, 5).into(),
+            cnt2_overflow: bit(2, 6).into(),
+            cnt2_aberr: bit(2, 7).into(),
+            cnt2_zero: bit(2, 5).into(),
+            power_status: (bit(0, 4) || bit(1, 4) || bit(2, 4)).into(),
+            ref_reg_status: bit(0, 3).into(),
+            upd_reg_status: bit(0, 2).into(),
+            ref_cnt_status: bit(0, 1).into(),
+            ext_err_status: bit(1, 3).into(),
+            ext_warn_status: bit(1, 2).into(),
+            comm_status: bit(1, 1).into(),
+            tp_status: bit(0, 0).into(),
+            tpi_status: bit(1, 0).into(),
+            ssi_enabled: bit(2, 0).into(),
+        }
+    }
+
+    /// Return `true` if the device reports no problems, `false` otherwise.
+    ///
+    /// The device is considered healthy if no counter or the reference counter has overflowed,
+    /// no decodification errors occured, no undervoltage reset took place, no communication
+    /// collision was detected, and no external errors or warnings are present.
+    ///
+    /// The following fields are intentionally ignored, as they report states and not problems:
+    /// zero status of the counters, validity of the reference and UPD registers, touch probe
+    /// status, TPI pin status, and the SSI enabled status.
+    pub fn is_healthy(&self) -> bool {
+        self.cnt0_overflow == OverflowStatus::Ok
+            && self.cnt1_overflow == OverflowStatus::Ok
+            && self.cnt2_overflow == OverflowStatus::Ok
+            && self.ref_cnt_status == OverflowStatus::Ok
+            && self.cnt0_aberr == DecodificationStatus::Ok
+            && self.cnt1_aberr == DecodificationStatus::Ok
+            && self.cnt2_aberr == DecodificationStatus::Ok
+            && self.power_status == UndervoltageStatus::Ok
+            && self.comm_status == CommunicationStatus::Ok
+            && self.ext_err_status == ErrorStatus::Ok
+            && self.ext_warn_status == WarningStatus::Ok
+    }
+
+    /// Get the device status from the external error and warning status.
+    ///
+    /// This is the status that `IcMd::get_full_device_status()` stores in the driver, such that
+    /// `IcMd::get_device_status()` is consistent with the last full status read.
+    pub fn to_device_status(&self) -> DeviceStatus {
+        DeviceStatus {
+            warning: self.ext_warn_status,
+            error: self.ext_err_status,
+        }
+    }
+
+    /// Return `true` if a power down (undervoltage) reset was detected, `false` otherwise.
+    ///
+    /// When read with `IcMd::get_full_device_status()`, this is the case if any of the three
+    /// status registers reported a power down. You might want to re-initialize the device then.
+    pub fn power_down_detected(&self) -> bool {
+        self.power_status == UndervoltageStatus::Undervoltage
+    }
+
+    /// Compare this status with a previous one and report which fields changed.
+    ///
+    /// This allows applications that poll the full device status to, e.g., only log transitions.
+    pub fn changes_since(&self, prev: &FullDeviceStatus) -> StatusChanges {
+        StatusChanges {
+            cnt0_overflow: self.cnt0_overflow != prev.cnt0_overflow,
+            cnt0_aberr: self.cnt0_aberr != prev.cnt0_aberr,
+            cnt0_zero: self.cnt0_zero != prev.cnt0_zero,
+            cnt1_overflow: self.cnt1_overflow != prev.cnt1_overflow,
+            cnt1_aberr: self.cnt1_aberr != prev.cnt1_aberr,
+            cnt1_zero: self.cnt1_zero != prev.cnt1_zero,
+            cnt2_overflow: self.cnt2_overflow != prev.cnt2_overflow,
+            cnt2_aberr: self.cnt2_aberr != prev.cnt2_aberr,
+            cnt2_zero: self.cnt2_zero != prev.cnt2_zero,
+            power_status: self.power_status != prev.power_status,
+            ref_reg_status: self.ref_reg_status != prev.ref_reg_status,
+            upd_reg_status: self.upd_reg_status != prev.upd_reg_status,
+            ref_cnt_status: self.ref_cnt_status != prev.ref_cnt_status,
+            ext_err_status: self.ext_err_status != prev.ext_err_status,
+            ext_warn_status: self.ext_warn_status != prev.ext_warn_status,
+            comm_status: self.comm_status != prev.comm_status,
+            tp_status: self.tp_status != prev.tp_status,
+            tpi_status: self.tpi_status != prev.tpi_status,
+            ssi_enabled: self.ssi_enabled != prev.ssi_enabled,
+        }
+    }
+
+    /// Get the status of a single counter channel.
+    ///
+    /// Returns the overflow, decodification, and zero status of the given counter (0, 1, or 2).
+    /// If the channel does not exist, `None` is returned.
+    pub fn counter_status(&self, channel: u8) -> Option<CounterStatus> {
+        match channel {
+            0 => Some(CounterStatus {
+                overflow: self.cnt0_overflow,
+                aberr: self.cnt0_aberr,
+                zero: self.cnt0_zero,
+            }),
+            1 => Some(CounterStatus {
+                overflow: self.cnt1_overflow,
+                aberr: self.cnt1_aberr,
+                zero: self.cnt1_zero,
+            }),
+            2 => Some(CounterStatus {
+                overflow: self.cnt2_overflow,
+                aberr: self.cnt2_aberr,
+                zero: self.cnt2_zero,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Status Changes
+///
+/// This struct indicates which fields of a `FullDeviceStatus` changed compared to a previous one.
+/// A field is `true` if the corresponding status changed, `false` otherwise.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct StatusChanges {
+    /// Overflow of counter 0 changed
+    pub cnt0_overflow: bool,
+    /// Decodification error of AB inputs in counter 0 changed
+    pub cnt0_aberr: bool,
+    /// Zero status of counter 0 changed
+    pub cnt0_zero: bool,
+    /// Overflow of counter 1 changed
+    pub cnt1_overflow: bool,
+    /// Decodification error of AB inputs in counter 1 changed
+    pub cnt1_aberr: bool,
+    /// Zero status of counter 1 changed
+    pub cnt1_zero: bool,
+    /// Overflow of counter 2 changed
+    pub cnt2_overflow: bool,
+    /// Decodification error of AB inputs in counter 2 changed
+    pub cnt2_aberr: bool,
+    /// Zero status of counter 2 changed
+    pub cnt2_zero: bool,
+    /// Power status changed
+    pub power_status: bool,
+    /// Reference register status changed
+    pub ref_reg_status: bool,
+    /// UPD register status changed
+    pub upd_reg_status: bool,
+    /// Reference counter status changed
+    pub ref_cnt_status: bool,
+    /// External error status changed
+    pub ext_err_status: bool,
+    /// External warning status changed
+    pub ext_warn_status: bool,
+    /// Communication status changed
+    pub comm_status: bool,
+    /// Touch probe status changed
+    pub tp_status: bool,
+    /// TPI pin status changed
+    pub tpi_status: bool,
+    /// SSI enabled status changed
+    pub ssi_enabled: bool,
+}
+
+impl StatusChanges {
+    /// Return `true` if any of the fields changed, `false` otherwise.
+    pub fn any(&self) -> bool {
+        self.cnt0_overflow
+            || self.cnt0_aberr
+            || self.cnt0_zero
+            || self.cnt1_overflow
+            || self.cnt1_aberr
+            || self.cnt1_zero
+            || self.cnt2_overflow
+            || self.cnt2_aberr
+            || self.cnt2_zero
+            || self.power_status
+            || self.ref_reg_status
+            || self.upd_reg_status
+            || self.ref_cnt_status
+            || self.ext_err_status
+            || self.ext_warn_status
+            || self.comm_status
+            || self.tp_status
+            || self.tpi_status
+            || self.ssi_enabled
+    }
+}
+
+/// Counter Status
+///
+/// This struct contains the status of a single counter channel as extracted from the
+/// `FullDeviceStatus`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CounterStatus {
+    /// Overflow of the counter
+    pub overflow: OverflowStatus,
+    /// Decodification error of AB inputs in the counter
+    pub aberr: DecodificationStatus,
+    /// Zero status of the counter
+    pub zero: ZeroStatus,
+}
+
 /// Actuator status.
 ///
 /// This struct is used to keep track of the status of the actuator pins. Upon first initialization
 /// they are both set to `PinStatus::Low`. The actuator pins are ACT0 and ACT1.
-#[derive(Debug, Default, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ActuatorStatus {
     /// Status of the ACT0 pin
     pub act0: PinStatus,
     /// Status of the ACT1 pin
     pub act1: PinStatus,
+    /// Recorded mode of the ACT0 pin
+    pub act0_mode: ActuatorMode,
+    /// Recorded mode of the ACT1 pin
+    pub act1_mode: ActuatorMode,
+}
+
+impl ActuatorStatus {
+    /// Create a new actuator status with both pins low in push-pull mode, same as the default.
+    pub(crate) const fn new() -> Self {
+        Self {
+            act0: PinStatus::Low,
+            act1: PinStatus::Low,
+            act0_mode: ActuatorMode::PushPull,
+            act1_mode: ActuatorMode::PushPull,
+        }
+    }
+
+    /// Check if the given actuator pin is active, taking its recorded mode into account.
+    pub fn is_active(&self, pin: ActuatorPin) -> bool {
+        match pin {
+            ActuatorPin::Act0 => self.act0_mode.is_active(&self.act0),
+            ActuatorPin::Act1 => self.act1_mode.is_active(&self.act1),
+        }
+    }
+}
+
+/// Instruction byte configuration
+///
+/// Holds all bits of the instruction byte (0x30), such that several instructions can be executed
+/// with a single write. The instructions are executed once when written, the actuator pins keep
+/// the written level. Use `From<ActuatorStatus>` to create a configuration without any
+/// instructions that keeps the current actuator pin levels.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InstructionByteConfig {
+    /// Reset counter 0
+    pub reset_cnt0: bool,
+    /// Reset counter 1
+    pub reset_cnt1: bool,
+    /// Reset counter 2
+    pub reset_cnt2: bool,
+    /// Enable zero codification
+    pub zero_codification: bool,
+    /// Load touch probe 2 with touch probe 1 value and touch probe 1 with counter 0 value
+    pub touch_probe: bool,
+    /// Level of the ACT0 pin
+    pub act0: PinStatus,
+    /// Level of the ACT1 pin
+    pub act1: PinStatus,
+}
+
+impl From<ActuatorStatus> for InstructionByteConfig {
+    fn from(val: ActuatorStatus) -> Self {
+        Self {
+            act0: val.act0,
+            act1: val.act1,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<InstructionByteConfig> for u8 {
+    fn from(val: InstructionByteConfig) -> Self {
+        (val.reset_cnt0 as u8)
+            | ((val.reset_cnt1 as u8) << 1)
+            | ((val.reset_cnt2 as u8) << 2)
+            | ((val.zero_codification as u8) << 3)
+            | ((val.touch_probe as u8) << 4)
+            | ((bool::from(&val.act0) as u8) << 5)
+            | ((bool::from(&val.act1) as u8) << 6)
+    }
+}
+
+/// Actuator mode
+///
+/// The electrical behavior of the actuator pins is fixed by the iC-MD. This mode only records the
+/// intent of how a pin is wired up, such that it can be determined if a pin is "active"
+/// regardless of its polarity. It is never written to the device.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ActuatorMode {
+    #[default]
+    /// Push-pull: The pin is active when driven high.
+    PushPull,
+    /// Open-drain: The pin is active when pulled low.
+    OpenDrain,
+}
+
+impl ActuatorMode {
+    /// Check if a pin in this mode is active at the given level.
+    pub fn is_active(&self, level: &PinStatus) -> bool {
+        match self {
+            ActuatorMode::PushPull => *level == PinStatus::High,
+            ActuatorMode::OpenDrain => *level == PinStatus::Low,
+        }
+    }
+}
+
+/// Touch probe value
+///
+/// Value of a touch probe register (TP1 or TP2) together with the touch probe status that was
+/// read right before the register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TouchProbeValue {
+    /// Captured counter 0 value, sign extended to the configured width of counter 0.
+    pub value: i64,
+    /// Touch probe status: Were the touch probe registers updated?
+    pub status: TouchProbeStatus,
+}
+
+/// Touch probe state
+///
+/// Touch probe status together with the level of the touch probe input pin TPI, e.g., for edge
+/// capture workflows.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TouchProbeState {
+    /// Touch probe status: Were the touch probe registers updated?
+    pub updated: TouchProbeStatus,
+    /// Level of the touch probe input pin TPI
+    pub tpi: PinStatus,
+}
+
+/// Reference and UPD register values
+///
+/// Values of the reference counter (REF) and the UPD register, e.g., for sine/cosine
+/// interpolation. Each value is only present if it was valid according to `Status0`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ReferenceAndUpd {
+    /// Reference counter value, `None` if it was not valid.
+    pub reference: Option<i32>,
+    /// UPD register value, `None` if it was not valid.
+    pub upd: Option<i32>,
+}
+
+/// Actuator pins
+///
+/// Enum to select one of the two actuator pins ACT0 and ACT1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ActuatorPin {
+    /// Actuator pin 0 (ACT0)
+    Act0,
+    /// Actuator pin 1 (ACT1)
+    Act1,
 }
 
 /// Warning Status
@@ -403,6 +1578,21 @@ impl From<bool> for OverflowStatus {
     }
 }
 
+/// Overflow Persistence
+///
+/// Enum that indicates if a counter overflow was flagged and if it persisted after clearing it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum OverflowPersistence {
+    #[default]
+    /// No overflow was flagged.
+    Ok,
+    /// An overflow was flagged, but it did not re-assert after clearing it.
+    Transient,
+    /// An overflow was flagged and re-asserted after clearing it.
+    Sustained,
+}
+
 /// Zero Status
 ///
 /// This enum indicates if the counter has reached the zero value or not.
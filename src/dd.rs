@@ -2,7 +2,8 @@
 //!
 //! Please refer to the iC-MD datasheet to better understand what each command does.
 
-use core::fmt::Debug;
+use core::fmt::{Debug, Display};
+use core::marker::PhantomData;
 
 use embedded_hal::spi::{Operation, SpiDevice};
 
@@ -22,6 +23,16 @@ device_driver::create_device! {
             const SIZE_BITS = 8;
             value: uint = 0..8,
         },
+        /// Operation mode
+        /// Configures the input type, the Z signal, and the touch probe operation. Here, the
+        /// operation mode is selected as a u8 value. The higher-level driver takes care of
+        /// converting from a meaningful configuration to the 8-bit value.
+        register OperationMode {
+            type Access = RW;
+            const ADDRESS = 0x01;
+            const SIZE_BITS = 8;
+            value: uint = 0..8,
+        },
         /// Read the 24 bit counter configuration, 24+2 bits to read (4 bytes)
         /// This corresponds to counter configuration `0b000`.
         register ReadCntCfg0 {
@@ -103,10 +114,10 @@ device_driver::create_device! {
             const SIZE_BITS = 56;
             const ALLOW_ADDRESS_OVERLAP = true;
 
-            /// Counter 1 value, bits 16-48
-            cnt1: int = 24..56,
-            /// Counter 0 value, bits 0-16
-            cnt0: int = 8..24,
+            /// Counter 1 value, bits 32-48
+            cnt1: int = 40..56,
+            /// Counter 0 value, bits 0-32
+            cnt0: int = 8..40,
             nerr: bool = 7,
             nwarn: bool = 6,
         },
@@ -132,7 +143,7 @@ device_driver::create_device! {
             type Access = RO;
             type ByteOrder = BE;
             const ADDRESS = 0x08;
-            const SIZE_BITS = 64;
+            const SIZE_BITS = 56;
             const ALLOW_ADDRESS_OVERLAP = true;
 
             /// Counter 2 value, bits 32-48
@@ -155,6 +166,41 @@ device_driver::create_device! {
             const SIZE_BITS = 24;
             value: int = 0..24,
         },
+        /// Read the UPD register 24 bits.
+        /// The UPD register is loaded with the counter value at the index pulse. Validity is
+        /// signaled by the `UpdVal` bit in `Status0`.
+        /// TODO: Same as for the reference register, this should be tested once the actual
+        /// hardware setup is available with an encoder connected.
+        register UpdRegister {
+            type Access = RO;
+            type ByteOrder = BE;
+            const ADDRESS = 0x14;
+            const SIZE_BITS = 24;
+            value: int = 0..24,
+        },
+        /// Read the touch probe 1 register 48 bits.
+        /// Loaded with the counter 0 value by the touch probe instruction or the TPI input.
+        /// The value is read unsigned, as the number of valid bits depends on the counter
+        /// configuration. The higher-level driver takes care of the sign extension.
+        /// TODO: Same as for the reference register, this should be tested once the actual
+        /// hardware setup is available.
+        register TouchProbe1 {
+            type Access = RO;
+            type ByteOrder = BE;
+            const ADDRESS = 0x18;
+            const SIZE_BITS = 48;
+            value: uint = 0..48,
+        },
+        /// Read the touch probe 2 register 48 bits.
+        /// Loaded with the touch probe 1 value by the touch probe instruction or the TPI input.
+        /// The value is read unsigned, see `TouchProbe1`.
+        register TouchProbe2 {
+            type Access = RO;
+            type ByteOrder = BE;
+            const ADDRESS = 0x20;
+            const SIZE_BITS = 48;
+            value: uint = 0..48,
+        },
         /// Instruction byte (write only)
         /// Allows writing of the instruction bytes. When one of these bits is set to 1, the
         /// corresponding instruction is executed and the bit set back to zero, except in the
@@ -186,6 +232,7 @@ device_driver::create_device! {
             type Access = RO;
             const ADDRESS = 0x48;
             const SIZE_BITS = 8;
+            const ALLOW_ADDRESS_OVERLAP = true;
 
             /// Touch probe registers TP1/TP2 loaded or new values loaded.
             TpVal: bool = 0,
@@ -218,6 +265,7 @@ device_driver::create_device! {
             type Access = RO;
             const ADDRESS = 0x49;
             const SIZE_BITS = 8;
+            const ALLOW_ADDRESS_OVERLAP = true;
 
             /// TPS signal: Status of the signal on input pin TPI.
             Tps: bool = 0,
@@ -273,30 +321,299 @@ device_driver::create_device! {
             /// too high or if two incrmeental edges are too close together.
             AbErr2: bool = 7,
         },
+        /// `StatusAll`: All three status registers read in a single transaction
+        /// Since the read address is auto-incremented, `Status0`, `Status1`, and `Status2` can be
+        /// read in one burst. `Status0` is the first byte read, `Status2` the last one. Please
+        /// refer to the individual status registers for the meaning of the bits.
+        register StatusAll {
+            type Access = RO;
+            type ByteOrder = BE;
+            const ADDRESS = 0x48;
+            const SIZE_BITS = 24;
+            const ALLOW_ADDRESS_OVERLAP = true;
+
+            /// `Status0`: Touch probe registers TP1/TP2 loaded or new values loaded.
+            TpVal: bool = 16,
+            /// `Status0`: Overflow of the reference counter.
+            OvfRef: bool = 17,
+            /// `Status0`: UPD value loaded.
+            UpdVal: bool = 18,
+            /// `Status0`: Reference value loaded.
+            RVal: bool = 19,
+            /// `Status0`: Power down.
+            PDwn0: bool = 20,
+            /// `Status0`: Zero of counter 0 reached.
+            Zero0: bool = 21,
+            /// `Status0`: Overflow of counter 0.
+            Ovf0: bool = 22,
+            /// `Status0`: AB input decodification error for counter 0.
+            AbErr0: bool = 23,
+            /// `Status1`: Status of input pin TPI.
+            Tps: bool = 8,
+            /// `Status1`: Communication collision took place.
+            ComCol1: bool = 9,
+            /// `Status1`: External warning.
+            ExtWarn1: bool = 10,
+            /// `Status1`: External error.
+            ExtErr1: bool = 11,
+            /// `Status1`: Power down.
+            PDwn1: bool = 12,
+            /// `Status1`: Zero of counter 1 reached.
+            Zero1: bool = 13,
+            /// `Status1`: Overflow of counter 1.
+            Ovf1: bool = 14,
+            /// `Status1`: AB input decodification error for counter 1.
+            AbErr1: bool = 15,
+            /// `Status2`: Status of the SSI pin.
+            EnSsi: bool = 0,
+            /// `Status2`: Communication collision took place.
+            ComCol2: bool = 1,
+            /// `Status2`: External warning.
+            ExtWarn2: bool = 2,
+            /// `Status2`: External error.
+            ExtErr2: bool = 3,
+            /// `Status2`: Power down.
+            PDwn2: bool = 4,
+            /// `Status2`: Zero of counter 2 reached.
+            Zero2: bool = 5,
+            /// `Status2`: Overflow of counter 2.
+            Ovf2: bool = 6,
+            /// `Status2`: AB input decodification error for counter 2.
+            AbErr2: bool = 7,
+        },
+        /// `Status01`: `Status0` and `Status1` read in a single transaction
+        /// `Status0` is the first byte read, `Status1` the second one. `Status2` is not read and
+        /// its status bits are therefore not reset. Please refer to the individual status
+        /// registers for the meaning of the bits.
+        register Status01 {
+            type Access = RO;
+            type ByteOrder = BE;
+            const ADDRESS = 0x48;
+            const SIZE_BITS = 16;
+            const ALLOW_ADDRESS_OVERLAP = true;
+
+            /// `Status0`: Touch probe registers TP1/TP2 loaded or new values loaded.
+            TpVal: bool = 8,
+            /// `Status0`: Overflow of the reference counter.
+            OvfRef: bool = 9,
+            /// `Status0`: UPD value loaded.
+            UpdVal: bool = 10,
+            /// `Status0`: Reference value loaded.
+            RVal: bool = 11,
+            /// `Status0`: Power down.
+            PDwn0: bool = 12,
+            /// `Status0`: Zero of counter 0 reached.
+            Zero0: bool = 13,
+            /// `Status0`: Overflow of counter 0.
+            Ovf0: bool = 14,
+            /// `Status0`: AB input decodification error for counter 0.
+            AbErr0: bool = 15,
+            /// `Status1`: Status of input pin TPI.
+            Tps: bool = 0,
+            /// `Status1`: Communication collision took place.
+            ComCol1: bool = 1,
+            /// `Status1`: External warning.
+            ExtWarn1: bool = 2,
+            /// `Status1`: External error.
+            ExtErr1: bool = 3,
+            /// `Status1`: Power down.
+            PDwn1: bool = 4,
+            /// `Status1`: Zero of counter 1 reached.
+            Zero1: bool = 5,
+            /// `Status1`: Overflow of counter 1.
+            Ovf1: bool = 6,
+            /// `Status1`: AB input decodification error for counter 1.
+            AbErr1: bool = 7,
+        },
+        /// `Status12`: `Status1` and `Status2` read in a single transaction
+        /// `Status1` is the first byte read, `Status2` the second one. `Status0` is not read and
+        /// its status bits are therefore not reset. Please refer to the individual status
+        /// registers for the meaning of the bits.
+        register Status12 {
+            type Access = RO;
+            type ByteOrder = BE;
+            const ADDRESS = 0x49;
+            const SIZE_BITS = 16;
+            const ALLOW_ADDRESS_OVERLAP = true;
 
+            /// `Status1`: Status of input pin TPI.
+            Tps: bool = 8,
+            /// `Status1`: Communication collision took place.
+            ComCol1: bool = 9,
+            /// `Status1`: External warning.
+            ExtWarn1: bool = 10,
+            /// `Status1`: External error.
+            ExtErr1: bool = 11,
+            /// `Status1`: Power down.
+            PDwn1: bool = 12,
+            /// `Status1`: Zero of counter 1 reached.
+            Zero1: bool = 13,
+            /// `Status1`: Overflow of counter 1.
+            Ovf1: bool = 14,
+            /// `Status1`: AB input decodification error for counter 1.
+            AbErr1: bool = 15,
+            /// `Status2`: Status of the SSI pin.
+            EnSsi: bool = 0,
+            /// `Status2`: Communication collision took place.
+            ComCol2: bool = 1,
+            /// `Status2`: External warning.
+            ExtWarn2: bool = 2,
+            /// `Status2`: External error.
+            ExtErr2: bool = 3,
+            /// `Status2`: Power down.
+            PDwn2: bool = 4,
+            /// `Status2`: Zero of counter 2 reached.
+            Zero2: bool = 5,
+            /// `Status2`: Overflow of counter 2.
+            Ovf2: bool = 6,
+            /// `Status2`: AB input decodification error for counter 2.
+            AbErr2: bool = 7,
+        },
+
+    }
+}
+
+/// Maximum SPI clock frequency in Hz supported by the iC-MD according to the datasheet.
+pub const MAX_SPI_HZ: u32 = 10_000_000;
+
+/// Register operation that is reported to the trace callback.
+#[cfg(feature = "trace")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TraceOperation {
+    /// Data was written to the register.
+    Write,
+    /// Data was read from the register.
+    Read,
+}
+
+/// Trace callback, called with the operation, the register address, and the data.
+#[cfg(feature = "trace")]
+pub type TraceFn<A = u8> = fn(TraceOperation, A, &[u8]);
+
+/// Maximum number of bytes that a register address occupies on the SPI bus.
+pub const MAX_ADDRESS_LEN: usize = 4;
+
+/// Flag that is set in the address byte to read a register, see [`read_address`].
+pub const READ_FLAG: u8 = 0x80;
+
+/// Get the address byte that is sent to read the register at the given address, e.g., to
+/// construct the expected SPI transactions in tests.
+pub const fn read_address(address: u8) -> u8 {
+    READ_FLAG | address
+}
+
+/// Register address that can be sent to the device over SPI.
+///
+/// The iC-MD uses `u8` addresses, where the highest bit selects a read access. Implement this
+/// trait for device variants with a different addressing scheme.
+pub trait RegisterAddress: Copy {
+    /// Encode the address for a read or write access into the given buffer and return the
+    /// number of bytes that were used.
+    fn encode(self, read: bool, buf: &mut [u8; MAX_ADDRESS_LEN]) -> usize;
+}
+
+impl RegisterAddress for u8 {
+    fn encode(self, read: bool, buf: &mut [u8; MAX_ADDRESS_LEN]) -> usize {
+        buf[0] = match read {
+            true => read_address(self),
+            false => self,
+        };
+        1
     }
 }
 
+/// Shape of the SPI transaction used to write registers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum WriteMode {
+    /// The address and the data are written as two separate operations in one transaction.
+    #[default]
+    Split,
+    /// The address and the data are concatenated and written as a single operation, for SPI
+    /// devices or adapters that require one contiguous write buffer. Writes of more than
+    /// `MAX_CONTIGUOUS_WRITE` data bytes fall back to `Split`.
+    Contiguous,
+}
+
+/// Byte order in which the counter registers arrive at the driver.
+///
+/// The iC-MD itself always sends the counter registers big-endian. Only use `LittleEndian` if an
+/// SPI bridge or adapter in your hardware path reorders the bytes, i.e., presents them LSB-first.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ByteOrderOverride {
+    /// The bytes arrive in the order sent by the iC-MD.
+    #[default]
+    BigEndian,
+    /// The bytes arrive in reversed order and are flipped before decoding.
+    LittleEndian,
+}
+
+/// Maximum number of data bytes that are written contiguously with `WriteMode::Contiguous`.
+pub const MAX_CONTIGUOUS_WRITE: usize = 7;
+
 /// The SPI Device wrapper interface to the driver
+///
+/// The address type defaults to the `u8` addresses of the iC-MD, see [`RegisterAddress`].
 #[derive(Debug)]
-pub struct DeviceInterface<Spi> {
+pub struct DeviceInterface<Spi, A = u8> {
     /// The SPI device used to communicate with the iC-MD device.
     pub spi: Spi,
+    /// Shape of the SPI transaction used to write registers.
+    write_mode: WriteMode,
+    /// Optional callback that is invoked for every register read and write.
+    #[cfg(feature = "trace")]
+    trace: Option<TraceFn<A>>,
+    _address: PhantomData<A>,
 }
 
 impl<Spi> DeviceInterface<Spi> {
     /// Construct a new instance of the device.
     ///
-    /// Spi mode 0, max 10 MHz according to the datasheet.
+    /// Spi mode 0, max 10 MHz according to the datasheet (see [`MAX_SPI_HZ`]).
     pub const fn new(spi: Spi) -> Self {
-        Self { spi }
+        Self::new_with_write_mode(spi, WriteMode::Split)
+    }
+
+    /// Construct a new instance of the device with the given write mode.
+    ///
+    /// See [`WriteMode`] for the available transaction shapes.
+    pub const fn new_with_write_mode(spi: Spi, write_mode: WriteMode) -> Self {
+        Self::new_with_address_type(spi, write_mode)
     }
 }
 
-impl<Spi: SpiDevice> device_driver::RegisterInterface for DeviceInterface<Spi> {
+impl<Spi, A> DeviceInterface<Spi, A> {
+    /// Construct a new instance of the device with the address type `A` and the given write
+    /// mode, e.g., for device variants with a different addressing scheme.
+    pub const fn new_with_address_type(spi: Spi, write_mode: WriteMode) -> Self {
+        Self {
+            spi,
+            write_mode,
+            #[cfg(feature = "trace")]
+            trace: None,
+            _address: PhantomData,
+        }
+    }
+
+    /// Set the trace callback, or remove it by passing `None`.
+    ///
+    /// The callback is invoked after every successful register write and read with the
+    /// operation, the register address, and the written or read data. This can help to diagnose
+    /// wiring or endianness issues during bring-up.
+    #[cfg(feature = "trace")]
+    pub fn set_trace(&mut self, trace: Option<TraceFn<A>>) {
+        self.trace = trace;
+    }
+}
+
+impl<Spi: SpiDevice, A: RegisterAddress> device_driver::RegisterInterface
+    for DeviceInterface<Spi, A>
+{
     type Error = DeviceError<Spi::Error>;
 
-    type AddressType = u8;
+    type AddressType = A;
 
     fn write_register(
         &mut self,
@@ -304,10 +621,27 @@ impl<Spi: SpiDevice> device_driver::RegisterInterface for DeviceInterface<Spi> {
         _size_bits: u32,
         data: &[u8],
     ) -> Result<(), Self::Error> {
-        Ok(SpiDevice::transaction(
-            &mut self.spi,
-            &mut [Operation::Write(&[address]), Operation::Write(data)],
-        )?)
+        let mut addr = [0u8; MAX_ADDRESS_LEN];
+        let addr_len = address.encode(false, &mut addr);
+        match self.write_mode {
+            WriteMode::Contiguous if data.len() <= MAX_CONTIGUOUS_WRITE => {
+                let mut buf = [0u8; MAX_ADDRESS_LEN + MAX_CONTIGUOUS_WRITE];
+                buf[..addr_len].copy_from_slice(&addr[..addr_len]);
+                buf[addr_len..addr_len + data.len()].copy_from_slice(data);
+                SpiDevice::write(&mut self.spi, &buf[..addr_len + data.len()])?;
+            }
+            _ => SpiDevice::transaction(
+                &mut self.spi,
+                &mut [Operation::Write(&addr[..addr_len]), Operation::Write(data)],
+            )?,
+        }
+
+        #[cfg(feature = "trace")]
+        if let Some(trace) = self.trace {
+            trace(TraceOperation::Write, address, data);
+        }
+
+        Ok(())
     }
 
     fn read_register(
@@ -316,11 +650,18 @@ impl<Spi: SpiDevice> device_driver::RegisterInterface for DeviceInterface<Spi> {
         _size_bits: u32,
         data: &mut [u8],
     ) -> Result<(), Self::Error> {
+        let mut addr = [0u8; MAX_ADDRESS_LEN];
+        let addr_len = address.encode(true, &mut addr);
         SpiDevice::transaction(
             &mut self.spi,
-            &mut [Operation::Write(&[0x80 | address]), Operation::Read(data)],
+            &mut [Operation::Write(&addr[..addr_len]), Operation::Read(data)],
         )?;
 
+        #[cfg(feature = "trace")]
+        if let Some(trace) = self.trace {
+            trace(TraceOperation::Read, address, data);
+        }
+
         Ok(())
     }
 }
@@ -330,12 +671,31 @@ impl<Spi: SpiDevice> device_driver::RegisterInterface for DeviceInterface<Spi> {
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct DeviceError<Spi>(pub Spi);
 
+impl<Spi> DeviceError<Spi> {
+    /// Consume the error and return the inner SPI error.
+    pub fn into_inner(self) -> Spi {
+        self.0
+    }
+}
+
 impl<Spi> From<Spi> for DeviceError<Spi> {
     fn from(value: Spi) -> Self {
         Self(value)
     }
 }
 
+impl<Spi: Display> Display for DeviceError<Spi> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "SPI error: {}", self.0)
+    }
+}
+
+impl<Spi: core::error::Error + 'static> core::error::Error for DeviceError<Spi> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
 impl<Spi> core::ops::Deref for DeviceError<Spi> {
     type Target = Spi;
 
@@ -0,0 +1,248 @@
+//! Module to hold the high-level error type of the driver
+
+use core::fmt::{Debug, Display};
+
+use crate::configs::ConfigSnapshot;
+use crate::dd::DeviceError;
+
+/// High-level driver error
+///
+/// Wraps the low-level [`DeviceError`] returned by the SPI interface and adds error conditions
+/// that are detected by the driver itself, e.g., when validating a configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum IcMdError<Spi> {
+    /// Error during SPI communication with the device.
+    Device(DeviceError<Spi>),
+    /// The given SPI clock frequency in Hz is above the maximum supported by the iC-MD.
+    SpiFrequencyTooHigh(u32),
+    /// The given counter channel is not configured in the current counter configuration.
+    InvalidChannel(u8),
+    /// The Z signal cannot be configured in the three counter configuration, as there are no
+    /// connections for Z signals available.
+    ZSignalNotSupported,
+    /// A communication collision was reported by the device on every attempt.
+    CommunicationCollision,
+    /// The given configuration profile index is out of range.
+    InvalidProfile(usize),
+    /// The given register byte cannot be converted into its typed representation.
+    InvalidRegisterValue(u8),
+    /// The NERR and NWARN bits of a counter read are both active, which indicates a framing
+    /// problem if the framing verification is enabled, e.g., a wiring or SPI mode mistake.
+    /// Contains the offending status byte.
+    Framing(u8),
+    /// All bytes of a counter read were 0xFF, which indicates a floating MISO line, e.g., a
+    /// disconnected bus.
+    BusFault,
+    /// The given index of a `ReadCntCfgN` register is out of range (0 to 7).
+    InvalidCntRegister(u8),
+    /// The counter did not reach the target value in time. Contains the last counter value.
+    Timeout(i64),
+    /// Differential inputs were selected with a counter configuration that only supports TTL
+    /// inputs, i.e., a configuration with more than one counter.
+    DifferentialNotSupported,
+    /// The configuration read back from the device does not match the written one.
+    ConfigMismatch {
+        /// Configuration that was written to the device
+        expected: ConfigSnapshot,
+        /// Configuration that was read back from the device
+        actual: ConfigSnapshot,
+    },
+}
+
+impl<Spi: Display> Display for IcMdError<Spi> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            IcMdError::Device(err) => write!(f, "{err}"),
+            IcMdError::SpiFrequencyTooHigh(hz) => {
+                write!(f, "SPI clock frequency of {hz} Hz is too high")
+            }
+            IcMdError::InvalidChannel(channel) => {
+                write!(f, "counter channel {channel} is not configured")
+            }
+            IcMdError::ZSignalNotSupported => {
+                write!(f, "Z signal not supported in this counter configuration")
+            }
+            IcMdError::CommunicationCollision => write!(f, "communication collision"),
+            IcMdError::InvalidProfile(index) => {
+                write!(f, "configuration profile {index} does not exist")
+            }
+            IcMdError::InvalidRegisterValue(value) => {
+                write!(f, "invalid register value {value:#04x}")
+            }
+            IcMdError::Framing(status) => {
+                write!(
+                    f,
+                    "unexpected framing of counter read, status byte {status:#04x}"
+                )
+            }
+            IcMdError::BusFault => write!(f, "probable bus fault, all bytes read as 0xFF"),
+            IcMdError::InvalidCntRegister(n) => {
+                write!(f, "counter read register ReadCntCfg{n} does not exist")
+            }
+            IcMdError::Timeout(value) => {
+                write!(
+                    f,
+                    "timeout while waiting for the counter, last value {value}"
+                )
+            }
+            IcMdError::DifferentialNotSupported => write!(
+                f,
+                "differential inputs are not supported with more than one counter"
+            ),
+            IcMdError::ConfigMismatch { expected, actual } => write!(
+                f,
+                "configuration mismatch, wrote {:#04x} {:#04x}, read back {:#04x} {:#04x}",
+                expected.counter_config,
+                expected.operation_mode,
+                actual.counter_config,
+                actual.operation_mode
+            ),
+        }
+    }
+}
+
+impl<Spi: core::error::Error + 'static> core::error::Error for IcMdError<Spi> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            IcMdError::Device(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Error of a read that was interrupted part way
+///
+/// Returned when an error, e.g., an SPI error, occurs in the middle of a series of reads.
+/// Contains the number of reads that were completed successfully before the error, as well as
+/// the error itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PartialReadError<Spi> {
+    /// Number of successful reads before the error occured.
+    pub read: usize,
+    /// The error that interrupted the reads.
+    pub error: IcMdError<Spi>,
+}
+
+impl<Spi: Display> Display for PartialReadError<Spi> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} after {} successful reads", self.error, self.read)
+    }
+}
+
+impl<Spi: core::error::Error + 'static> core::error::Error for PartialReadError<Spi> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Invalid register value
+///
+/// Returned when converting a raw register byte into its typed representation fails, e.g.,
+/// because a reserved bit is set. Contains the offending byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InvalidRegisterValue(pub u8);
+
+/// Counter configuration error
+///
+/// Returned when validating a counter configuration that contains settings which do not take
+/// effect on the device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// The Z signal of the given counter channel is set to a non-default value, but Z signals
+    /// are ignored in this configuration.
+    ZSignalIgnored(u8),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ConfigError::ZSignalIgnored(channel) => write!(
+                f,
+                "Z signal of counter {channel} is ignored in this counter configuration"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for ConfigError {}
+
+/// Counter value error
+///
+/// Returned when accessing a counter channel of a `CntCount` by its index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum CountError {
+    /// The given channel index is out of range, the iC-MD has only the channels 0 to 2.
+    OutOfRange(u8),
+    /// The given channel is not configured in the counter configuration of the read.
+    NotConfigured(u8),
+}
+
+impl Display for CountError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CountError::OutOfRange(channel) => {
+                write!(f, "counter channel {channel} is out of range (0 to 2)")
+            }
+            CountError::NotConfigured(channel) => {
+                write!(f, "counter channel {channel} is not configured")
+            }
+        }
+    }
+}
+
+impl core::error::Error for CountError {}
+
+/// Parse error
+///
+/// Returned when parsing a configuration from a string fails, e.g., when reading a config file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The string does not name a counter configuration.
+    UnknownCounterConfig,
+    /// The string does not name a counting direction.
+    UnknownDirection,
+    /// The string does not name a Z signal setup.
+    UnknownZSignal,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseError::UnknownCounterConfig => write!(
+                f,
+                "unknown counter configuration, expected one of 1x24, 2x24, 1x48, 1x16, 1x32, \
+                 32+16, 2x16, or 3x16"
+            ),
+            ParseError::UnknownDirection => {
+                write!(f, "unknown counting direction, expected cw or ccw")
+            }
+            ParseError::UnknownZSignal => {
+                write!(f, "unknown Z signal setup, expected normal or inverted")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+impl<Spi> From<InvalidRegisterValue> for IcMdError<Spi> {
+    fn from(value: InvalidRegisterValue) -> Self {
+        Self::InvalidRegisterValue(value.0)
+    }
+}
+
+impl<Spi> From<DeviceError<Spi>> for IcMdError<Spi> {
+    fn from(value: DeviceError<Spi>) -> Self {
+        Self::Device(value)
+    }
+}
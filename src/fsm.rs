@@ -0,0 +1,105 @@
+//! Module to hold the state machine for non-blocking counter reads
+
+use crate::configs::{CntCfg, CntCount, CounterFrame, DeviceStatus};
+use crate::dd::read_address;
+
+/// Result of feeding a byte to a `CounterReadFsm`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CounterReadPoll {
+    /// More bytes are required to complete the counter read.
+    Pending,
+    /// The counter read is complete and the decoded counter values are available.
+    Ready(CntCount),
+}
+
+/// Phase of a counter read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+enum Phase {
+    /// The read address is transmitted, the received byte is ignored.
+    Address,
+    /// The counter bytes are received, the transmitted bytes are ignored by the device.
+    Data,
+}
+
+/// State machine for non-blocking counter reads, e.g., with DMA or interrupt driven SPI.
+///
+/// The state machine sequences the address-write and the data-read phase of a counter read
+/// without a blocking `SpiDevice`. For every byte of the transfer, transmit `tx_byte()` and feed
+/// the byte that was received at the same time to `feed()`. Once all bytes of the configured
+/// counters arrived, the decoded counter values are returned and the state machine starts over
+/// for the next read. You are responsible for asserting the chip select during one read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CounterReadFsm {
+    config: CntCfg,
+    phase: Phase,
+    frame: CounterFrame,
+    received: usize,
+    device_status: Option<DeviceStatus>,
+}
+
+impl CounterReadFsm {
+    /// Create a new state machine that reads the counters with the given configuration.
+    pub fn new(config: CntCfg) -> Self {
+        Self {
+            config,
+            phase: Phase::Address,
+            frame: CounterFrame::new(config.read_len()),
+            received: 0,
+            device_status: None,
+        }
+    }
+
+    /// Get the byte to transmit next: The read address of the counters in the address phase, a
+    /// dummy byte in the data phase.
+    pub fn tx_byte(&self) -> u8 {
+        match self.phase {
+            Phase::Address => read_address(0x08),
+            Phase::Data => 0x00,
+        }
+    }
+
+    /// Get the total number of bytes of one counter read, including the address byte.
+    pub fn transfer_len(&self) -> usize {
+        1 + self.config.read_len()
+    }
+
+    /// Feed the byte that was received while transmitting `tx_byte()`.
+    ///
+    /// Returns `Pending` until the last byte of the counter read arrived, then the decoded
+    /// counter values. The device status of the completed read is available via
+    /// `device_status()` afterwards.
+    ///
+    /// # Arguments
+    /// * `byte`: The received byte.
+    pub fn feed(&mut self, byte: u8) -> CounterReadPoll {
+        if self.phase == Phase::Address {
+            self.phase = Phase::Data;
+            return CounterReadPoll::Pending;
+        }
+        self.frame.as_bytes_mut()[self.received] = byte;
+        self.received += 1;
+        if self.received < self.config.read_len() {
+            return CounterReadPoll::Pending;
+        }
+
+        self.device_status = Some(DeviceStatus::from_frame_status(self.frame.status()));
+        let count = self.config.decode(self.frame.as_bytes());
+        self.reset();
+        CounterReadPoll::Ready(count)
+    }
+
+    /// Abort the current counter read and start over with the address phase.
+    pub fn reset(&mut self) {
+        self.phase = Phase::Address;
+        self.received = 0;
+    }
+
+    /// Get the device status (NWARN and NERR) of the last completed counter read, `None` if no
+    /// read was completed yet.
+    pub fn device_status(&self) -> Option<DeviceStatus> {
+        self.device_status
+    }
+}
@@ -0,0 +1,86 @@
+//! Module to hold the builder that combines several instructions into a single write
+
+use embedded_hal::spi::SpiDevice;
+
+use crate::IcMd;
+use crate::configs::{InstructionByteConfig, PinStatus};
+use crate::dd::DeviceError;
+
+/// Builder for the instruction byte (0x30).
+///
+/// Obtain it from `IcMd::instructions()`, chain the instructions that should be executed, and
+/// write them to the device in a single transaction with `commit()`. The actuator pins start out
+/// at their cached levels, such that they are only changed if explicitly requested.
+#[derive(Debug)]
+pub struct InstructionBuilder<'a, Spi> {
+    icmd: &'a mut IcMd<Spi>,
+    config: InstructionByteConfig,
+}
+
+impl<'a, Spi: SpiDevice> InstructionBuilder<'a, Spi> {
+    /// Create a new builder that keeps the cached actuator pin levels of the driver.
+    pub(crate) fn new(icmd: &'a mut IcMd<Spi>) -> Self {
+        let config = InstructionByteConfig::from(icmd.get_actuator_status());
+        Self { icmd, config }
+    }
+
+    /// Reset counter 0.
+    pub fn reset_counter0(mut self) -> Self {
+        self.config.reset_cnt0 = true;
+        self
+    }
+
+    /// Reset counter 1.
+    pub fn reset_counter1(mut self) -> Self {
+        self.config.reset_cnt1 = true;
+        self
+    }
+
+    /// Reset counter 2.
+    pub fn reset_counter2(mut self) -> Self {
+        self.config.reset_cnt2 = true;
+        self
+    }
+
+    /// Enable the zero codification.
+    pub fn zero_codification(mut self) -> Self {
+        self.config.zero_codification = true;
+        self
+    }
+
+    /// Load touch probe 2 with the touch probe 1 value and touch probe 1 with the counter 0
+    /// value.
+    pub fn touch_probe(mut self) -> Self {
+        self.config.touch_probe = true;
+        self
+    }
+
+    /// Set the level of the ACT0 pin.
+    ///
+    /// # Arguments
+    /// * `level`: The new level of the pin.
+    pub fn actuator0(mut self, level: PinStatus) -> Self {
+        self.config.act0 = level;
+        self
+    }
+
+    /// Set the level of the ACT1 pin.
+    ///
+    /// # Arguments
+    /// * `level`: The new level of the pin.
+    pub fn actuator1(mut self, level: PinStatus) -> Self {
+        self.config.act1 = level;
+        self
+    }
+
+    /// Get the instruction byte configuration that would be written by `commit()`.
+    pub fn config(&self) -> InstructionByteConfig {
+        self.config
+    }
+
+    /// Write all chained instructions to the device in a single transaction.
+    /// See `IcMd::write_instruction()` for details.
+    pub fn commit(self) -> Result<(), DeviceError<Spi::Error>> {
+        self.icmd.write_instruction(self.config)
+    }
+}
@@ -24,16 +24,16 @@
 //!
 //! # Limitations
 //!
-//! The following features are currently only accessible via the low-level interface:
+//! The following features are available, but it is unclear if they currently work:
 //!
-//! - Reference register readout: It is unclear if this currently works, see code comment.
+//! - Reference register readout via `read_reference_and_upd()`, see code comment.
 //!
-//! The following features are currently not yet implemented:
+//! The following features are currently only available as raw values in `OperationMode`:
 //!
-//! - Differential or TTL inputs (Address 0x01, bit 7)
-//! - Configuration to have Z signal clear counters 0 and/or 1 (Address 0x01, bits 5 and 6)
-//! - Z signal configuration (Address 0x01, bits 3 and 4)
 //! - Touch probe and AB registers (Address 0x01, bits 1 and 2)
+//!
+//! The following features are currently not yet implemented:
+//!
 //! - Differential input configuration selection (RS-422 (default) or LVDS) (Address 0x03, bit 7)
 //!
 //! # Example Usage
@@ -44,7 +44,7 @@
 //! # let expectations = [
 //! #     Transaction::transaction_start(),
 //! #     Transaction::write(0x00),
-//! #     Transaction::write(0x02),
+//! #     Transaction::write_vec(vec![0x02, 0x00]),
 //! #     Transaction::transaction_end(),
 //! #     Transaction::transaction_start(),
 //! #     Transaction::write(0x80 | 0x08),
@@ -79,6 +79,18 @@
 //! # spi_device.done();
 //! ```
 //!
+//! # Shared SPI bus
+//!
+//! The driver takes ownership of an `SpiDevice`, which manages the chip select of the iC-MD. If
+//! the SPI bus is shared with other devices, use one of the `SpiDevice` implementations of the
+//! [`embedded-hal-bus`](https://crates.io/crates/embedded-hal-bus) crate, e.g., a
+//! `RefCellDevice`, and pass it to `IcMd::new()`. The bus is then only borrowed for the duration
+//! of each transaction. See `tests/shared_bus.rs` for an example.
+//!
+//! If you only have an `SpiBus` and drive the chip select pin yourself, enable the
+//! `embedded-hal-bus` feature and create the driver with `IcMd::from_bus()`, which frames every
+//! transaction with the chip select pin. See `tests/bus_device.rs` for an example.
+//!
 //! # Further help
 //!
 //! For further help and examples, please have a look at the `test` directory in the GitHub
@@ -90,14 +102,32 @@
 #![cfg_attr(not(test), no_std)]
 
 use core::{fmt::Debug, result::Result};
-use embedded_hal::spi::SpiDevice;
+use device_driver::RegisterInterface;
+use embedded_hal::{
+    delay::DelayNs,
+    spi::{ErrorKind, SpiDevice},
+};
 
-use dd::{Device, DeviceError, DeviceInterface};
+use dd::{ByteOrderOverride, Device, DeviceError, DeviceInterface, MAX_SPI_HZ, WriteMode};
 
+pub use alerts::*;
 pub use configs::*;
+pub use error::*;
+pub use fsm::*;
+pub use instruction::*;
+pub use motion::*;
+pub use session::*;
 
+pub mod alerts;
+#[cfg(feature = "embedded-hal-bus")]
+mod bus;
 pub mod configs;
 pub mod dd;
+pub mod error;
+pub mod fsm;
+pub mod instruction;
+pub mod motion;
+pub mod session;
 
 /// The main driver struct of the crate representing the iC-MD quadrature counter.
 /// You can also access the underlying device driver directly via the `device` field.
@@ -108,37 +138,229 @@ pub struct IcMd<Spi> {
     pub device: Device<DeviceInterface<Spi>>,
     /// Configuration of the counter, set only prior to calling `init()`.
     counter_config: CntCfg,
+    /// Operation mode of the device, set only prior to calling `init()`.
+    operation_mode: OperationMode,
     /// Status of the device (error and warning flags). Read only, updated when reading the
     /// counter.
     device_status: DeviceStatus,
     actuator_status: ActuatorStatus,
+    /// Latched when a status read observes a power down (`PDwn`), cleared by `init()`.
+    needs_reconfiguration: bool,
+    /// Report reads of all 0xFF as bus fault in `read_counter()`.
+    detect_bus_fault: bool,
+    /// Report reads with NERR and NWARN both active as framing error in `read_counter()`.
+    verify_framing: bool,
+    /// Kind of the error that occured when an `IcMdSession` was dropped.
+    session_error: Option<ErrorKind>,
+    /// Byte order of the counter registers as they arrive at the driver.
+    byte_order: ByteOrderOverride,
+    /// Instruction byte that was last written to the device.
+    last_instruction: u8,
+    /// User defined ID to distinguish multiple devices in log output.
+    id: Option<u8>,
+    /// Result of the last `overflow_since_last_check()`.
+    overflow_flagged: bool,
+    /// Sequence number of the next `read_counter_seq()`.
+    sequence: u32,
+}
+
+impl<Spi> IcMd<Spi> {
+    /// The default counter configuration of the driver: One 48-bit counter, counting clockwise
+    /// with a normal Z signal.
+    pub const DEFAULT_CONFIG: CntCfg =
+        CntCfg::Cnt1Bit48(CntSetup::new(CntDirection::CW, CntZSignal::Normal));
+
+    /// The power-on counter configuration of the iC-MD according to the datasheet: One 24-bit
+    /// counter, counting clockwise with a normal Z signal. Note that this differs from the
+    /// default configuration of the driver (`DEFAULT_CONFIG`).
+    pub const POWER_ON_CONFIG: CntCfg =
+        CntCfg::Cnt1Bit24(CntSetup::new(CntDirection::CW, CntZSignal::Normal));
+
+    /// The interval in microseconds in which `wait_for_count()` polls the counter.
+    pub const WAIT_POLL_INTERVAL_US: u32 = 100;
+
+    /// The interval in microseconds between the samples of `read_status_debounced()`.
+    pub const DEBOUNCE_INTERVAL_US: u32 = 10;
+}
+
+/// Summarizes the active counter configuration and the cached status of the driver.
+#[cfg(feature = "defmt")]
+impl<Spi> defmt::Format for IcMd<Spi> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "IcMd {{ id: {}, counter_config: {}, device_status: {}, actuator_status: {} }}",
+            self.id,
+            self.counter_config,
+            self.device_status,
+            self.actuator_status
+        );
+    }
 }
 
 impl<Spi: SpiDevice> IcMd<Spi> {
     /// Creates a new instance of the iC-MD driver.
     /// By default, the counter is configured to 48-bit mode.
-    pub fn new(spi: Spi) -> Self {
+    /// This is a `const fn`, such that the driver can be created in a `static` or `const`
+    /// context, given that the SPI device can be created there as well.
+    pub const fn new(spi: Spi) -> Self {
+        Self::new_with_write_mode(spi, WriteMode::Split)
+    }
+
+    /// Creates a new instance of the iC-MD driver with the given write mode.
+    /// Use `WriteMode::Contiguous` if your SPI device or adapter requires the register address
+    /// and the data to be written in a single buffer. Otherwise, this is the same as `new()`.
+    ///
+    /// # Arguments
+    /// * `spi`: The SPI device to use.
+    /// * `write_mode`: The shape of the SPI transaction used to write registers.
+    pub const fn new_with_write_mode(spi: Spi, write_mode: WriteMode) -> Self {
         Self {
-            device: Device::new(DeviceInterface::new(spi)),
-            counter_config: CntCfg::Cnt1Bit48(CntSetup::default()),
-            actuator_status: ActuatorStatus::default(),
-            device_status: DeviceStatus::default(),
+            device: Device::new(DeviceInterface::new_with_write_mode(spi, write_mode)),
+            counter_config: Self::DEFAULT_CONFIG,
+            operation_mode: OperationMode::new(),
+            actuator_status: ActuatorStatus::new(),
+            device_status: DeviceStatus::new(),
+            needs_reconfiguration: false,
+            detect_bus_fault: false,
+            verify_framing: false,
+            session_error: None,
+            byte_order: ByteOrderOverride::BigEndian,
+            last_instruction: 0,
+            id: None,
+            overflow_flagged: false,
+            sequence: 0,
         }
     }
 
+    /// Creates a new instance of the iC-MD driver after checking the SPI clock frequency.
+    /// The iC-MD requires SPI mode 0 with a clock of at most 10 MHz. Since the `SpiDevice` does
+    /// not expose its configuration, you have to pass the clock frequency (in Hz) that you
+    /// configured your SPI bus with. If it is too high, an error is returned.
+    ///
+    /// # Arguments
+    /// * `spi`: The SPI device to use.
+    /// * `spi_hz`: The configured SPI clock frequency in Hz.
+    pub fn new_checked(spi: Spi, spi_hz: u32) -> Result<Self, IcMdError<Spi::Error>> {
+        if spi_hz > Self::max_spi_hz() {
+            return Err(IcMdError::SpiFrequencyTooHigh(spi_hz));
+        }
+        Ok(Self::new(spi))
+    }
+
+    /// Get the maximum SPI clock frequency in Hz that is supported by the iC-MD.
+    pub const fn max_spi_hz() -> u32 {
+        MAX_SPI_HZ
+    }
+
+    /// Release the SPI device.
+    /// This consumes the driver and returns the underlying SPI device, e.g., to use it for other
+    /// peripherals on the same bus.
+    pub fn release(self) -> Spi {
+        self.device.interface.spi
+    }
+
+    /// Set an ID for this device, e.g., to distinguish multiple counters in log output.
+    /// The ID is purely driver-side bookkeeping and shows up in the `Debug` and `defmt::Format`
+    /// output of the driver. No SPI communication takes place.
+    ///
+    /// # Arguments
+    /// * `id`: The ID of this device.
+    pub const fn with_id(mut self, id: u8) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Get the ID of this device, `None` if no ID was set with `with_id()`.
+    pub fn id(&self) -> Option<u8> {
+        self.id
+    }
+
+    /// Set a trace callback that is invoked for every register read and write.
+    /// Pass `None` to remove the callback. Only available with the `trace` feature.
+    #[cfg(feature = "trace")]
+    pub fn set_trace(&mut self, trace: Option<dd::TraceFn>) {
+        self.device.interface.set_trace(trace);
+    }
+
     /// Initialize the iC-MD device with the given configuration.
-    pub fn init(&mut self) -> Result<(), DeviceError<Spi::Error>> {
-        self.device
-            .counter_configuration()
-            .write(|reg| reg.set_value(self.counter_config.into()))?;
+    /// The counter configuration (0x00) and the operation mode (0x01) are written in a single
+    /// transaction, making use of the address auto-increment of the iC-MD.
+    ///
+    /// Configurations with more than one counter only support TTL inputs. If differential inputs
+    /// are selected in the operation mode with such a configuration, a `DifferentialNotSupported`
+    /// error is returned without writing to the device. Note that differential inputs are the
+    /// default, so set the input type to `InputType::Ttl` for these configurations.
+    pub fn init(&mut self) -> Result<(), IcMdError<Spi::Error>> {
+        let config: [u8; 2] = [self.counter_config.into(), self.operation_mode.into()];
+        // Check the input type bit (bit 7 of the operation mode) that is actually written
+        if self.counter_config.num_counters() > 1
+            && config[1] >> 7 == u8::from(InputType::Differential)
+        {
+            return Err(IcMdError::DifferentialNotSupported);
+        }
+        self.device.interface.write_register(0x00, 16, &config)?;
 
+        self.needs_reconfiguration = false;
         Ok(())
     }
 
+    /// Initialize the device and verify that the configuration was latched.
+    /// This combines `init()` and `verify_config()`, e.g., to catch a brown-out of the device
+    /// during the initialization.
+    pub fn init_verified(&mut self) -> Result<(), IcMdError<Spi::Error>> {
+        self.init()?;
+        self.verify_config()
+    }
+
+    /// Read the configuration back from the device and compare it with the cached one.
+    /// The counter configuration (0x00) and the operation mode (0x01) are read in a single
+    /// transaction, see `snapshot_config()`. If they differ from the cached configuration, a
+    /// `ConfigMismatch` error with both configurations is returned.
+    pub fn verify_config(&mut self) -> Result<(), IcMdError<Spi::Error>> {
+        let expected = ConfigSnapshot {
+            counter_config: self.counter_config.into(),
+            operation_mode: self.operation_mode.into(),
+        };
+        let actual = self.snapshot_config()?;
+        if actual != expected {
+            return Err(IcMdError::ConfigMismatch { expected, actual });
+        }
+        Ok(())
+    }
+
+    /// Check if the device needs to be reconfigured.
+    /// This flag is latched whenever a status read of the driver observes a power down (`PDwn`)
+    /// in any of the status registers, i.e., the device was reset due to an undervoltage and lost
+    /// its configuration. It is cleared by a successful `init()`. No status is read by this
+    /// function.
+    pub fn needs_reconfiguration(&self) -> bool {
+        self.needs_reconfiguration
+    }
+
+    /// Get the kind of the error that occured when the last `IcMdSession` ended.
+    /// Returns `None` if the counters were reset successfully or no reset was requested.
+    pub fn session_error(&self) -> Option<ErrorKind> {
+        self.session_error
+    }
+
+    /// Reset the iC-MD device to its power-on configuration.
+    /// The counter configuration (0x00) and the operation mode (0x01) registers are rewritten
+    /// with their documented reset values and the cached configuration is updated accordingly.
+    /// See `POWER_ON_CONFIG` for the counter configuration after the reset.
+    pub fn reset_to_defaults(&mut self) -> Result<(), IcMdError<Spi::Error>> {
+        self.counter_config = Self::POWER_ON_CONFIG;
+        self.operation_mode = OperationMode::new();
+        self.init()
+    }
+
     /// Set the actuator pins output to the given status.
     /// Note that as far as the iC-MD is concerned, this status is "write only". Thus, there is no
     /// function available to read the current status of the actuator pins. However, the stored
     /// `actuator_status` variable will be updated according to what you set here.
+    /// The actuator pins share the instruction byte (0x30) with the counter reset and touch probe
+    /// instructions. Every instruction that the driver writes re-applies the cached actuator
+    /// status, such that the pins keep their level regardless of the call order.
     ///
     /// # Arguments
     /// * `act0`: The status of actuator pin 0 (ACT0).
@@ -148,101 +370,808 @@ impl<Spi: SpiDevice> IcMd<Spi> {
         act0: &PinStatus,
         act1: &PinStatus,
     ) -> Result<(), DeviceError<Spi::Error>> {
-        self.device.instruction_byte().write(|reg| {
-            reg.set_act_0(act0.into());
-            reg.set_act_1(act1.into());
-        })?;
-        self.actuator_status.act0 = *act0;
-        self.actuator_status.act1 = *act1;
+        self.write_instruction(InstructionByteConfig {
+            act0: *act0,
+            act1: *act1,
+            ..Default::default()
+        })
+    }
+
+    /// Set the actuator pins output to the given status and record the mode of each pin.
+    /// The mode is not written to the iC-MD, as the electrical behavior of the pins is fixed.
+    /// It is stored alongside the pin status, such that `ActuatorStatus::is_active()` reports if
+    /// a pin is active regardless of its polarity. The SPI write is the same as for
+    /// `configure_actuator_pins()`.
+    ///
+    /// # Arguments
+    /// * `act0`: The status of actuator pin 0 (ACT0).
+    /// * `act0_mode`: The mode of actuator pin 0 (ACT0).
+    /// * `act1`: The status of actuator pin 1 (ACT1).
+    /// * `act1_mode`: The mode of actuator pin 1 (ACT1).
+    pub fn configure_actuator_pins_with_mode(
+        &mut self,
+        act0: &PinStatus,
+        act0_mode: ActuatorMode,
+        act1: &PinStatus,
+        act1_mode: ActuatorMode,
+    ) -> Result<(), DeviceError<Spi::Error>> {
+        self.configure_actuator_pins(act0, act1)?;
+        self.actuator_status.act0_mode = act0_mode;
+        self.actuator_status.act1_mode = act1_mode;
         Ok(())
     }
 
+    /// Set the actuator pins to complementary levels, e.g., to drive two relays inversely.
+    /// ACT0 is set to the given level and ACT1 to the inverse level in a single write. The
+    /// cached actuator status is updated for both pins.
+    ///
+    /// # Arguments
+    /// * `primary`: The status of actuator pin 0 (ACT0), ACT1 gets the inverse status.
+    pub fn set_actuators_complementary(
+        &mut self,
+        primary: PinStatus,
+    ) -> Result<(), DeviceError<Spi::Error>> {
+        let inverse = match primary {
+            PinStatus::High => PinStatus::Low,
+            PinStatus::Low => PinStatus::High,
+        };
+        self.configure_actuator_pins(&primary, &inverse)
+    }
+
+    /// Get the stored status of the actuator pins.
+    pub fn get_actuator_status(&self) -> ActuatorStatus {
+        self.actuator_status
+    }
+
+    /// Pulse an actuator pin.
+    /// The given actuator pin is set high, kept high for the given duration, and then returned to
+    /// its previous level. The level of the other actuator pin is preserved in both writes.
+    ///
+    /// # Arguments
+    /// * `pin`: The actuator pin to pulse.
+    /// * `duration_us`: The duration of the pulse in microseconds.
+    /// * `delay`: The delay provider used to wait for the duration of the pulse.
+    pub fn pulse_actuator<D: DelayNs>(
+        &mut self,
+        pin: ActuatorPin,
+        duration_us: u32,
+        delay: &mut D,
+    ) -> Result<(), DeviceError<Spi::Error>> {
+        let previous = self.actuator_status;
+        let (act0, act1) = match pin {
+            ActuatorPin::Act0 => (PinStatus::High, previous.act1),
+            ActuatorPin::Act1 => (previous.act0, PinStatus::High),
+        };
+        self.configure_actuator_pins(&act0, &act1)?;
+        delay.delay_us(duration_us);
+        self.configure_actuator_pins(&previous.act0, &previous.act1)
+    }
+
     /// Get current device status.
-    /// This is a cached value that is updated when reading the counter. It contains the error and
-    /// warning flags of the device. For a full device status, use `get_full_device_status()`.
+    /// This is a cached value that is updated when reading the counter or the full device
+    /// status. It contains the error and warning flags of the device. For a full device status,
+    /// use `get_full_device_status()`.
     pub fn get_device_status(&self) -> DeviceStatus {
         self.device_status
     }
 
-    /// Get the full device status by reading all the status registers.
+    /// Get the full device status by reading all the status registers in a single transaction.
     /// This will reset many of the status bits to wait for the next event, problem, issue to
     /// occur.
+    /// The power down bit is present in all three status registers. They should agree, but might
+    /// differ during transients. Therefore, the power status reports an undervoltage if any of
+    /// the three registers reports it.
+    /// The cached device status is updated with the external error and warning status, see
+    /// `FullDeviceStatus::to_device_status()`.
     pub fn get_full_device_status(&mut self) -> Result<FullDeviceStatus, DeviceError<Spi::Error>> {
-        let status0 = self.device.status_0().read()?;
-        let status1 = self.device.status_1().read()?;
+        let status = self.device.status_all().read()?;
+        self.latch_power_down(status.p_dwn_0() || status.p_dwn_1() || status.p_dwn_2());
+
+        let full_status = FullDeviceStatus {
+            cnt0_overflow: status.ovf_0().into(),
+            cnt0_aberr: status.ab_err_0().into(),
+            cnt0_zero: status.zero_0().into(),
+            cnt1_overflow: status.ovf_1().into(),
+            cnt1_aberr: status.ab_err_1().into(),
+            cnt1_zero: status.zero_1().into(),
+            cnt2_overflow: status.ovf_2().into(),
+            cnt2_aberr: status.ab_err_2().into(),
+            cnt2_zero: status.zero_2().into(),
+            power_status: (status.p_dwn_0() || status.p_dwn_1() || status.p_dwn_2()).into(),
+            ref_reg_status: status.r_val().into(),
+            upd_reg_status: status.upd_val().into(),
+            ref_cnt_status: status.ovf_ref().into(),
+            ext_err_status: status.ext_err_1().into(),
+            ext_warn_status: status.ext_warn_1().into(),
+            comm_status: status.com_col_1().into(),
+            tp_status: status.tp_val().into(),
+            tpi_status: status.tps().into(),
+            ssi_enabled: status.en_ssi().into(),
+        };
+        self.device_status = full_status.to_device_status();
+        Ok(full_status)
+    }
+
+    /// Read the full device status several times and only report the bits that are set in the
+    /// majority of the samples, e.g., to filter transient noise on the status lines.
+    /// The samples are spaced by `DEBOUNCE_INTERVAL_US` microseconds. A bit is considered set if
+    /// it is set in more than half of the samples, so use an odd number of samples to avoid ties,
+    /// which are resolved as not set. If zero samples are requested, a single sample is read.
+    ///
+    /// Note that every read resets many of the status bits on the device, so a latched event is
+    /// only reported if it is set again in the majority of the samples. As with
+    /// `get_full_device_status()`, a power down seen in any sample is latched for
+    /// `needs_reconfiguration()`, and the cached device status is updated.
+    ///
+    /// # Arguments
+    /// * `samples`: The number of status reads to vote on.
+    /// * `delay`: The delay provider to space the reads.
+    pub fn read_status_debounced<D: DelayNs>(
+        &mut self,
+        samples: u8,
+        delay: &mut D,
+    ) -> Result<FullDeviceStatus, DeviceError<Spi::Error>> {
+        let samples = samples.max(1);
+        let mut votes = [[0u8; 8]; 3];
+        for sample in 0..samples {
+            if sample > 0 {
+                delay.delay_us(Self::DEBOUNCE_INTERVAL_US);
+            }
+            let raw = self.read_raw_status()?;
+            for (byte, counts) in raw.iter().zip(votes.iter_mut()) {
+                for (bit, count) in counts.iter_mut().enumerate() {
+                    *count += (byte >> bit) & 1;
+                }
+            }
+        }
+
+        let mut majority = [0u8; 3];
+        for (byte, counts) in majority.iter_mut().zip(votes.iter()) {
+            for (bit, count) in counts.iter().enumerate() {
+                if *count > samples / 2 {
+                    *byte |= 1 << bit;
+                }
+            }
+        }
+        let full_status = FullDeviceStatus::from_raw(majority);
+        self.device_status = full_status.to_device_status();
+        Ok(full_status)
+    }
+
+    /// Read the full device status and log every problem via `defmt::warn!`.
+    /// The fields that are logged are the same that are checked by
+    /// `FullDeviceStatus::is_healthy()`. The full device status is returned, such that it can
+    /// still be used by the caller. Only available with the `defmt` feature.
+    #[cfg(feature = "defmt")]
+    pub fn poll_and_log_status(&mut self) -> Result<FullDeviceStatus, DeviceError<Spi::Error>> {
+        let status = self.get_full_device_status()?;
+        let overflows = [
+            status.cnt0_overflow,
+            status.cnt1_overflow,
+            status.cnt2_overflow,
+        ];
+        for (channel, overflow) in overflows.iter().enumerate() {
+            if *overflow != OverflowStatus::Ok {
+                defmt::warn!("iC-MD: Overflow of counter {}", channel);
+            }
+        }
+        let aberrs = [status.cnt0_aberr, status.cnt1_aberr, status.cnt2_aberr];
+        for (channel, aberr) in aberrs.iter().enumerate() {
+            if *aberr != DecodificationStatus::Ok {
+                defmt::warn!("iC-MD: Decodification error of counter {}", channel);
+            }
+        }
+        if status.ref_cnt_status != OverflowStatus::Ok {
+            defmt::warn!("iC-MD: Overflow of the reference counter");
+        }
+        if status.power_status != UndervoltageStatus::Ok {
+            defmt::warn!("iC-MD: Undervoltage reset");
+        }
+        if status.comm_status != CommunicationStatus::Ok {
+            defmt::warn!("iC-MD: Communication collision");
+        }
+        if status.ext_err_status != ErrorStatus::Ok {
+            defmt::warn!("iC-MD: External error");
+        }
+        if status.ext_warn_status != WarningStatus::Ok {
+            defmt::warn!("iC-MD: External warning");
+        }
+        Ok(status)
+    }
+
+    /// Check if any counter reports an AB decodification error.
+    /// The `AbErr` bits of the three counters are located in `Status0`, `Status1`, and
+    /// `Status2`, which are read in a single transaction. No status is decoded apart from the
+    /// decodification errors, which makes this cheaper than `get_full_device_status()`.
+    ///
+    /// Note that reading the status registers resets the status bits of all three registers, as
+    /// with `get_full_device_status()`.
+    pub fn any_decodification_error(&mut self) -> Result<bool, DeviceError<Spi::Error>> {
+        let status = self.device.status_all().read()?;
+        self.latch_power_down(status.p_dwn_0() || status.p_dwn_1() || status.p_dwn_2());
+        Ok(status.ab_err_0() || status.ab_err_1() || status.ab_err_2())
+    }
+
+    /// Clear the overflow flags of all counters and check if an overflow persists.
+    /// The status registers are read in a single transaction, which clears the latched overflow
+    /// bits (`Ovf0`, `Ovf1`, `Ovf2`). If any overflow was flagged, the status registers are read a
+    /// second time to check if the overflow re-asserted. This distinguishes a transient from a
+    /// sustained overflow.
+    ///
+    /// Note that reading the status registers resets all latched status bits, as with
+    /// `get_full_device_status()`.
+    pub fn clear_overflows_checked(
+        &mut self,
+    ) -> Result<OverflowPersistence, DeviceError<Spi::Error>> {
+        if !self.read_any_overflow()? {
+            return Ok(OverflowPersistence::Ok);
+        }
+        match self.read_any_overflow()? {
+            true => Ok(OverflowPersistence::Sustained),
+            false => Ok(OverflowPersistence::Transient),
+        }
+    }
+
+    /// Check if any counter overflowed since the last call, e.g., in a supervisory loop.
+    /// The status registers are read in a single transaction, which clears the latched overflow
+    /// bits (`Ovf0`, `Ovf1`, `Ovf2`). Thus, a subsequent call only reports fresh overflows. The
+    /// result is cached and can be queried with `overflow_flagged()` without SPI communication.
+    ///
+    /// Note that reading the status registers resets all latched status bits, as with
+    /// `get_full_device_status()`.
+    pub fn overflow_since_last_check(&mut self) -> Result<bool, DeviceError<Spi::Error>> {
+        self.overflow_flagged = self.read_any_overflow()?;
+        Ok(self.overflow_flagged)
+    }
+
+    /// Get the result of the last `overflow_since_last_check()` without SPI communication.
+    /// Returns `false` if no check was done yet.
+    pub fn overflow_flagged(&self) -> bool {
+        self.overflow_flagged
+    }
+
+    /// Arm the overflow monitoring, e.g., right after homing.
+    /// The full device status is read, which clears the latched overflow bits of all counters,
+    /// such that any overflow that is flagged afterwards occured after arming. The overflow
+    /// status of the three counters before clearing is returned in channel order.
+    ///
+    /// Note that reading the status registers resets all latched status bits, as with
+    /// `get_full_device_status()`.
+    pub fn arm_overflow_monitoring(
+        &mut self,
+    ) -> Result<[OverflowStatus; 3], DeviceError<Spi::Error>> {
+        let status = self.get_full_device_status()?;
+        Ok([
+            status.cnt0_overflow,
+            status.cnt1_overflow,
+            status.cnt2_overflow,
+        ])
+    }
+
+    /// Read the zero status of all three counters, e.g., for homing routines.
+    /// The `Zero` bits of the three counters are located in `Status0`, `Status1`, and `Status2`,
+    /// which are read in a single transaction. The zero status is returned in channel order.
+    ///
+    /// Note that reading the status registers resets the latched status bits of all three
+    /// registers, as with `get_full_device_status()`.
+    pub fn read_zero_events(&mut self) -> Result<[ZeroStatus; 3], DeviceError<Spi::Error>> {
+        let status = self.device.status_all().read()?;
+        self.latch_power_down(status.p_dwn_0() || status.p_dwn_1() || status.p_dwn_2());
+        Ok([
+            status.zero_0().into(),
+            status.zero_1().into(),
+            status.zero_2().into(),
+        ])
+    }
+
+    /// Clear the zero latch of a single counter, e.g., to home several axes independently.
+    /// Each counter has its `Zero` bit in its own status register (`Status0`, `Status1`, or
+    /// `Status2`). Only this register is read, which clears the latch of the given counter, but
+    /// leaves the latches of the other counters untouched. The zero status that was read is
+    /// returned.
+    ///
+    /// Note that all other status bits of the read register are reset as well, as with
+    /// `get_full_device_status()`.
+    ///
+    /// # Arguments
+    /// * `channel`: The counter channel (0, 1, or 2) whose zero latch to clear.
+    pub fn clear_zero(&mut self, channel: u8) -> Result<ZeroStatus, IcMdError<Spi::Error>> {
+        let (zero, p_dwn) = match channel {
+            0 => {
+                let status = self.device.status_0().read()?;
+                (status.zero_0(), status.p_dwn())
+            }
+            1 => {
+                let status = self.device.status_1().read()?;
+                (status.zero_1(), status.p_dwn())
+            }
+            2 => {
+                let status = self.device.status_2().read()?;
+                (status.zero_2(), status.p_dwn())
+            }
+            _ => return Err(IcMdError::InvalidChannel(channel)),
+        };
+        self.latch_power_down(p_dwn);
+        Ok(zero.into())
+    }
+
+    /// Check if the SSI interface is enabled by reading `Status2`.
+    /// The SSI interface cannot be enabled or disabled in software, it is controlled in hardware
+    /// via the SLI pin: If the pin is open, SSI is enabled. If the pin is closed, SSI is disabled.
+    ///
+    /// Note that reading `Status2` resets its status bits, as with `get_full_device_status()`.
+    pub fn is_ssi_enabled(&mut self) -> Result<bool, DeviceError<Spi::Error>> {
         let status2 = self.device.status_2().read()?;
+        self.latch_power_down(status2.p_dwn());
+        Ok(status2.en_ssi())
+    }
+
+    /// Read the level of the touch probe input pin TPI from `Status1`.
+    /// This is cheaper than a full status read and can be used to poll an external trigger line.
+    ///
+    /// Note that reading `Status1` resets its status bits, as with `get_full_device_status()`.
+    pub fn read_tpi(&mut self) -> Result<PinStatus, DeviceError<Spi::Error>> {
+        let status1 = self.device.status_1().read()?;
+        self.latch_power_down(status1.p_dwn());
+        Ok(status1.tps().into())
+    }
+
+    /// Check for communication collisions by reading `ComCol` from `Status1` and `Status2`.
+    /// Both registers are read in a single transaction. A collision is reported if either of
+    /// them has `ComCol` set, e.g., to detect contention on the bus.
+    ///
+    /// Note that reading `Status1` and `Status2` resets their status bits, which clears both
+    /// collision flags, as with `get_full_device_status()`.
+    pub fn communication_status(&mut self) -> Result<CommunicationStatus, DeviceError<Spi::Error>> {
+        let status = self.device.status_12().read()?;
+        self.latch_power_down(status.p_dwn_1() || status.p_dwn_2());
+        Ok((status.com_col_1() || status.com_col_2()).into())
+    }
 
-        Ok(FullDeviceStatus {
-            cnt0_overflow: status0.ovf_0().into(),
-            cnt0_aberr: status0.ab_err_0().into(),
-            cnt0_zero: status0.zero_0().into(),
-            cnt1_overflow: status1.ovf_1().into(),
-            cnt1_aberr: status1.ab_err_1().into(),
-            cnt1_zero: status1.zero_1().into(),
-            cnt2_overflow: status2.ovf_2().into(),
-            cnt2_aberr: status2.ab_err_2().into(),
-            cnt2_zero: status2.zero_2().into(),
-            power_status: status0.p_dwn().into(),
-            ref_reg_status: status0.r_val().into(),
-            upd_reg_status: status0.upd_val().into(),
-            ref_cnt_status: status0.ovf_ref().into(),
-            ext_err_status: status1.ext_err().into(),
-            ext_warn_status: status1.ext_warn().into(),
-            comm_status: status1.com_col().into(),
-            tp_status: status0.tp_val().into(),
-            tpi_status: status1.tps().into(),
-            ssi_enabled: status2.en_ssi().into(),
+    /// Read the touch probe status (`TpVal`) and the level of the TPI pin together.
+    /// Only `Status0` and `Status1` are read in a single transaction, which is cheaper than a full
+    /// status read and does not decode the other status bits.
+    ///
+    /// Note that reading `Status0` and `Status1` resets their status bits, as with
+    /// `get_full_device_status()`.
+    pub fn touch_probe_state(&mut self) -> Result<TouchProbeState, DeviceError<Spi::Error>> {
+        let status = self.device.status_01().read()?;
+        self.latch_power_down(status.p_dwn_0() || status.p_dwn_1());
+        Ok(TouchProbeState {
+            updated: status.tp_val().into(),
+            tpi: status.tps().into(),
         })
     }
 
+    /// Read the raw bytes of the three status registers `Status0`, `Status1`, and `Status2`.
+    /// All three registers are read in a single transaction.
+    /// The bytes are returned without any decoding, e.g., to dump them to a log during bring-up.
+    /// As with `get_full_device_status()`, reading the status registers resets many of the
+    /// status bits.
+    pub fn read_raw_status(&mut self) -> Result<[u8; 3], DeviceError<Spi::Error>> {
+        let mut raw = [0u8; 3];
+        self.device.interface.read_register(0x48, 24, &mut raw)?;
+        self.latch_power_down(raw.iter().any(|byte| byte & 0x10 != 0));
+        Ok(raw)
+    }
+
+    /// Read the raw counter configuration byte (register 0x00) as reported by the device.
+    /// The byte is returned without any decoding, e.g., to check the exact bits during bring-up.
+    /// The cached counter configuration of the driver is not changed.
+    pub fn read_config_byte(&mut self) -> Result<u8, DeviceError<Spi::Error>> {
+        Ok(self.device.counter_configuration().read()?.value())
+    }
+
     /// Read the current counter value and return it.
-    pub fn read_counter(&mut self) -> Result<CntCount, DeviceError<Spi::Error>> {
-        match self.counter_config {
-            CntCfg::Cnt1Bit24(_) => {
+    /// If the bus fault detection is enabled with `set_detect_bus_fault()`, a read of all 0xFF
+    /// returns a `BusFault` error. If the framing verification is enabled with
+    /// `set_verify_framing()`, a read with NERR and NWARN both active returns a `Framing` error.
+    pub fn read_counter(&mut self) -> Result<CntCount, IcMdError<Spi::Error>> {
+        if !self.detect_bus_fault && !self.verify_framing {
+            return Ok(self.read_counter_as(self.counter_config)?);
+        }
+        let frame = self.read_frame()?;
+        if self.detect_bus_fault && frame.as_bytes().iter().all(|byte| *byte == 0xFF) {
+            return Err(IcMdError::BusFault);
+        }
+        let status = frame.status();
+        if self.verify_framing && status & 0xC0 == 0 {
+            return Err(IcMdError::Framing(status));
+        }
+        self.device_status = DeviceStatus::from_frame_status(status);
+        Ok(self.counter_config.decode(frame.as_bytes()))
+    }
+
+    /// Read the current counter value together with a sequence number, e.g., to correlate the
+    /// samples in a log and to detect dropped samples downstream.
+    /// The sequence number starts at zero and is incremented with every successful call, wrapping
+    /// around at `u32::MAX`. Failed reads do not consume a sequence number.
+    pub fn read_counter_seq(&mut self) -> Result<(u32, CntCount), IcMdError<Spi::Error>> {
+        let count = self.read_counter()?;
+        let sequence = self.sequence;
+        self.sequence = self.sequence.wrapping_add(1);
+        Ok((sequence, count))
+    }
+
+    /// Read the current counter value and transform it with the given function.
+    /// This allows to post-process the read inline, e.g., to convert the counter values into
+    /// engineering units, without binding the intermediate `CntCount`.
+    ///
+    /// # Arguments
+    /// * `f`: The function that transforms the counter value.
+    pub fn read_counter_map<F, T>(&mut self, f: F) -> Result<T, IcMdError<Spi::Error>>
+    where
+        F: FnOnce(CntCount) -> T,
+    {
+        self.read_counter().map(f)
+    }
+
+    /// Measure the time that a number of successive counter reads take, e.g., to evaluate the
+    /// maximum safe encoder frequency of your setup.
+    /// A `DelayNs` provider can only wait, but not measure time. Therefore, pass a function that
+    /// returns the current value of a free running timer of your microcontroller in ticks. The
+    /// elapsed ticks are computed with wrapping arithmetic, so a single wrap-around of the timer
+    /// during the benchmark is handled.
+    ///
+    /// # Arguments
+    /// * `count`: The number of counter reads to perform.
+    /// * `now`: Function that returns the current timer value in ticks.
+    pub fn benchmark_reads<F: FnMut() -> u32>(
+        &mut self,
+        count: u32,
+        mut now: F,
+    ) -> Result<u32, IcMdError<Spi::Error>> {
+        let start = now();
+        for _ in 0..count {
+            self.read_counter()?;
+        }
+        Ok(now().wrapping_sub(start))
+    }
+
+    /// Read the `ReadCntCfgN` register with the given index and return the decoded counters.
+    /// This is meant for advanced users that switch between counter configurations frequently.
+    /// The index corresponds to the configuration bits of the counter configuration register,
+    /// see `CntCfg`. As with `read_counter_as()`, the cached counter configuration is not
+    /// changed, but the device status is updated.
+    ///
+    /// # Arguments
+    /// * `n`: Index of the register to read, from 0 to 7.
+    pub fn read_cfg_register(&mut self, n: u8) -> Result<CntCount, IcMdError<Spi::Error>> {
+        if n > 7 {
+            return Err(IcMdError::InvalidCntRegister(n));
+        }
+        Ok(self.read_counter_as(CntCfg::from(n))?)
+    }
+
+    /// Read all eight `ReadCntCfgN` registers and return the decoded counters of each, e.g., to
+    /// compare how the counter data decodes under different configurations during bring-up.
+    /// The array is indexed by the configuration bits, see `read_cfg_register()`. The registers
+    /// are read one after the other, so a moving counter is captured at different times. The
+    /// cached counter configuration is not changed, the device status is updated with each read.
+    pub fn read_all_layouts(&mut self) -> Result<[CntCount; 8], DeviceError<Spi::Error>> {
+        let mut layouts = [CntCount::Cnt1Bit24(0); 8];
+        for (cfg, layout) in layouts.iter_mut().enumerate() {
+            *layout = self.read_counter_as(CntCfg::from(cfg as u8))?;
+        }
+        Ok(layouts)
+    }
+
+    /// Read the current counter value assuming the given counter configuration.
+    /// The counter values are decoded with the given configuration instead of the cached one,
+    /// e.g., to probe the counters after a suspected power-on reset of the device. The cached
+    /// counter configuration is not changed, however, the device status is updated as with
+    /// `read_counter()`.
+    ///
+    /// # Arguments
+    /// * `config`: The counter configuration to decode the counter values with.
+    pub fn read_counter_as(&mut self, config: CntCfg) -> Result<CntCount, DeviceError<Spi::Error>> {
+        if self.byte_order == ByteOrderOverride::LittleEndian {
+            let frame = self.read_frame_as(config)?;
+            self.device_status = DeviceStatus::from_frame_status(frame.status());
+            return Ok(config.decode(frame.as_bytes()));
+        }
+        let (nwarn, nerr, count) = match config.count_register() {
+            CntRegister::ReadCntCfg0 => {
                 let res = self.device.read_cnt_cfg_0().read()?;
-                self.set_device_status(res.nwarn(), res.nerr());
-                Ok(CntCount::Cnt1Bit24(res.cnt_0()))
+                (res.nwarn(), res.nerr(), CntCount::Cnt1Bit24(res.cnt_0()))
             }
-            CntCfg::Cnt2Bit24(_, _) => {
+            CntRegister::ReadCntCfg1 => {
                 let res = self.device.read_cnt_cfg_1().read()?;
-                self.set_device_status(res.nwarn(), res.nerr());
-                Ok(CntCount::Cnt2Bit24(res.cnt_0(), res.cnt_1()))
+                let count = CntCount::Cnt2Bit24(res.cnt_0(), res.cnt_1());
+                (res.nwarn(), res.nerr(), count)
             }
-            CntCfg::Cnt1Bit48(_) => {
+            CntRegister::ReadCntCfg2 => {
                 let res = self.device.read_cnt_cfg_2().read()?;
-                self.set_device_status(res.nwarn(), res.nerr());
-                Ok(CntCount::Cnt1Bit48(res.cnt_0()))
+                (res.nwarn(), res.nerr(), CntCount::Cnt1Bit48(res.cnt_0()))
             }
-            CntCfg::Cnt1Bit16(_) => {
+            CntRegister::ReadCntCfg3 => {
                 let res = self.device.read_cnt_cfg_3().read()?;
-                self.set_device_status(res.nwarn(), res.nerr());
-                Ok(CntCount::Cnt1Bit16(res.cnt_0()))
+                (res.nwarn(), res.nerr(), CntCount::Cnt1Bit16(res.cnt_0()))
             }
-            CntCfg::Cnt1Bit32(_) => {
+            CntRegister::ReadCntCfg4 => {
                 let res = self.device.read_cnt_cfg_4().read()?;
-                self.set_device_status(res.nwarn(), res.nerr());
-                Ok(CntCount::Cnt1Bit32(res.cnt_0()))
+                (res.nwarn(), res.nerr(), CntCount::Cnt1Bit32(res.cnt_0()))
             }
-            CntCfg::Cnt2Bit32Bit16(_, _) => {
+            CntRegister::ReadCntCfg5 => {
                 let res = self.device.read_cnt_cfg_5().read()?;
-                self.set_device_status(res.nwarn(), res.nerr());
-                Ok(CntCount::Cnt2Bit32Bit16(res.cnt_0(), res.cnt_1()))
+                let count = CntCount::Cnt2Bit32Bit16(res.cnt_0(), res.cnt_1());
+                (res.nwarn(), res.nerr(), count)
             }
-            CntCfg::Cnt2Bit16(_, _) => {
+            CntRegister::ReadCntCfg6 => {
                 let res = self.device.read_cnt_cfg_6().read()?;
-                self.set_device_status(res.nwarn(), res.nerr());
-                Ok(CntCount::Cnt2Bit16(res.cnt_0(), res.cnt_1()))
+                let count = CntCount::Cnt2Bit16(res.cnt_0(), res.cnt_1());
+                (res.nwarn(), res.nerr(), count)
             }
-            CntCfg::Cnt3Bit16(_, _, _) => {
+            CntRegister::ReadCntCfg7 => {
                 let res = self.device.read_cnt_cfg_7().read()?;
-                self.set_device_status(res.nwarn(), res.nerr());
-                Ok(CntCount::Cnt3Bit16(res.cnt_0(), res.cnt_1(), res.cnt_2()))
+                let count = CntCount::Cnt3Bit16(res.cnt_0(), res.cnt_1(), res.cnt_2());
+                (res.nwarn(), res.nerr(), count)
+            }
+        };
+        self.set_device_status(nwarn, nerr);
+        Ok(count)
+    }
+
+    /// Fill the given buffer with successive counter reads, e.g., to log a short burst of data.
+    /// The reads are spaced by the given interval, no delay is added after the last read.
+    /// The number of reads, i.e., the length of the buffer, is returned.
+    /// If a read fails, e.g., due to an SPI error, the reads are stopped. The error contains the
+    /// number of samples that were collected so far, which are available at the start of the
+    /// buffer.
+    ///
+    /// # Arguments
+    /// * `buf`: The buffer to fill with counter values.
+    /// * `delay`: The delay provider to space the reads.
+    /// * `interval_us`: The interval between two reads in microseconds.
+    pub fn read_many<D: DelayNs>(
+        &mut self,
+        buf: &mut [CntCount],
+        delay: &mut D,
+        interval_us: u32,
+    ) -> Result<usize, PartialReadError<Spi::Error>> {
+        for (read, sample) in buf.iter_mut().enumerate() {
+            if read > 0 {
+                delay.delay_us(interval_us);
             }
+            *sample = self
+                .read_counter()
+                .map_err(|error| PartialReadError { read, error })?;
         }
+        Ok(buf.len())
+    }
+
+    /// Poll a counter until it reaches or passes the given target value, e.g., for simple
+    /// positioning.
+    /// The counter is read every `WAIT_POLL_INTERVAL_US` microseconds. The direction is
+    /// determined from the first read: If the counter starts below the target, it must reach a
+    /// value greater or equal than the target, otherwise a value less or equal than the target.
+    /// The counter value that reached the target is returned. A wrap-around of the counter is not
+    /// taken into account.
+    ///
+    /// The timeout only accounts for the delays between the reads, not for the time the reads
+    /// themselves take. If it elapses, a `Timeout` error with the last counter value is returned.
+    ///
+    /// # Arguments
+    /// * `channel`: The counter channel (0, 1, or 2) to poll.
+    /// * `target`: The target counter value.
+    /// * `delay`: The delay provider to space the reads.
+    /// * `timeout_us`: The timeout in microseconds.
+    pub fn wait_for_count<D: DelayNs>(
+        &mut self,
+        channel: u8,
+        target: i64,
+        delay: &mut D,
+        timeout_us: u32,
+    ) -> Result<i64, IcMdError<Spi::Error>> {
+        let mut value = self.read_channel(channel)?;
+        let increasing = value <= target;
+        let mut waited = 0;
+        loop {
+            if (increasing && value >= target) || (!increasing && value <= target) {
+                return Ok(value);
+            }
+            if waited >= timeout_us {
+                return Err(IcMdError::Timeout(value));
+            }
+            delay.delay_us(Self::WAIT_POLL_INTERVAL_US);
+            waited = waited.saturating_add(Self::WAIT_POLL_INTERVAL_US);
+            value = self.read_channel(channel)?;
+        }
+    }
+
+    /// Enable or disable the bus fault detection of `read_counter()`.
+    /// If MISO floats high, e.g., because the bus is disconnected, all bytes of a read come back
+    /// as 0xFF. With the detection enabled, such reads return a `BusFault` error instead of
+    /// counter values of -1. This is a heuristic: A floating MISO line might also be pulled low
+    /// or pick up noise, which is not detected. Conversely, a device whose counters all read -1
+    /// without error or warning can produce an all 0xFF read as well, which is then rejected.
+    /// By default, the detection is disabled.
+    ///
+    /// # Arguments
+    /// * `enabled`: If true, the bus fault detection is enabled.
+    pub fn set_detect_bus_fault(&mut self, enabled: bool) {
+        self.detect_bus_fault = enabled;
+    }
+
+    /// Enable or disable the framing verification of `read_counter()`, e.g., during bring-up.
+    /// The only documented bits of the status byte of a counter read are NERR (bit 7) and NWARN
+    /// (bit 6), which are active low. If the read is misaligned, e.g., due to an endianness, SPI
+    /// mode, or wiring mistake, or if MISO is stuck low, these positions typically hold zeros.
+    /// With the verification enabled, a read with NERR and NWARN both active is therefore not
+    /// decoded and a `Framing` error is returned. This is a heuristic: A device that really
+    /// reports an error and a warning at the same time is rejected as well, in which case
+    /// `get_full_device_status()` tells you more. Misaligned reads with any of the two bits
+    /// high are not detected. By default, the verification is disabled.
+    ///
+    /// # Arguments
+    /// * `enabled`: If true, the framing verification is enabled.
+    pub fn set_verify_framing(&mut self, enabled: bool) {
+        self.verify_framing = enabled;
+    }
+
+    /// Set the byte order in which the counter registers arrive at the driver.
+    /// The iC-MD itself is big-endian. Only override the byte order if an SPI bridge or adapter
+    /// in your hardware path reorders the bytes. With `ByteOrderOverride::LittleEndian`, the
+    /// bytes of all counter reads are flipped back before decoding and the counter values are
+    /// decoded by the driver instead of the device driver registers.
+    ///
+    /// # Arguments
+    /// * `byte_order`: The byte order of the counter registers.
+    pub fn set_byte_order_override(&mut self, byte_order: ByteOrderOverride) {
+        self.byte_order = byte_order;
+    }
+
+    /// Read the current counter values directly into the given buffer.
+    /// This avoids constructing a `CntCount` for the most performance critical paths. The values
+    /// are written in channel order, starting at index 0, and the number of configured channels
+    /// is returned. Entries of channels that are not configured are left untouched. The device
+    /// status is updated as with `read_counter()`.
+    ///
+    /// # Arguments
+    /// * `buf`: Buffer to write the counter values to.
+    pub fn read_counter_into(
+        &mut self,
+        buf: &mut [i64; 3],
+    ) -> Result<usize, DeviceError<Spi::Error>> {
+        let frame = self.read_frame()?;
+        self.device_status = DeviceStatus::from_frame_status(frame.status());
+        let config = self.counter_config;
+        let mut written = 0;
+        for (channel, value) in buf.iter_mut().enumerate() {
+            if let Some(val) = config.decode_channel(frame.as_bytes(), channel as u8) {
+                *value = val;
+                written += 1;
+            }
+        }
+        Ok(written)
+    }
+
+    /// Read the current counter value and return it together with the raw bytes of the read.
+    /// The counter value is decoded as with `read_counter()` and the device status is updated.
+    /// The raw bytes can be logged, e.g., to correlate the decoded values with the wire during
+    /// debugging.
+    pub fn read_counter_raw(
+        &mut self,
+    ) -> Result<(CntCount, CounterFrame), DeviceError<Spi::Error>> {
+        let frame = self.read_frame()?;
+        self.device_status = DeviceStatus::from_frame_status(frame.status());
+        Ok((self.counter_config.decode(frame.as_bytes()), frame))
+    }
+
+    /// Read the current counter value, retrying on communication collisions.
+    /// If the counter read reports a warning, `Status1` is read to check for a communication
+    /// collision (`ComCol`). In case of a collision, the read is retried. If no collision
+    /// occured, the counter value is returned, even if a warning for another reason is present.
+    /// At least one attempt is made, even if `max_attempts` is zero.
+    ///
+    /// Note that reading `Status1` resets its status bits, as with `get_full_device_status()`.
+    ///
+    /// # Arguments
+    /// * `max_attempts`: The maximum number of counter reads before giving up.
+    pub fn read_counter_retry(
+        &mut self,
+        max_attempts: u8,
+    ) -> Result<CntCount, IcMdError<Spi::Error>> {
+        for _ in 0..max_attempts.max(1) {
+            let count = self.read_counter()?;
+            if self.device_status.warning == WarningStatus::Ok {
+                return Ok(count);
+            }
+            let status1 = self.device.status_1().read()?;
+            self.latch_power_down(status1.p_dwn());
+            if !status1.com_col() {
+                return Ok(count);
+            }
+        }
+        Err(IcMdError::CommunicationCollision)
+    }
+
+    /// Read the UPD register.
+    /// First, `Status0` is read to check if the UPD register was loaded (`UpdVal`). If so, the
+    /// UPD register is read and its sign extended value returned as `Some(value)`. Otherwise,
+    /// `None` is returned and the UPD register is not read.
+    ///
+    /// Note that reading `Status0` resets its status bits, as with `get_full_device_status()`.
+    pub fn read_upd(&mut self) -> Result<Option<i32>, DeviceError<Spi::Error>> {
+        let status0 = self.device.status_0().read()?;
+        self.latch_power_down(status0.p_dwn());
+        if !status0.upd_val() {
+            return Ok(None);
+        }
+        let upd = self.device.upd_register().read()?;
+        Ok(Some(upd.value()))
+    }
+
+    /// Check if the reference value was loaded by reading `RVal` from `Status0`.
+    /// The reference value is loaded by the zero codification. After power-on, `RVal` remains 0
+    /// until the second distinct index pulse was detected, as the distance between two index
+    /// pulses is required to determine the reference. Use this, e.g., in homing routines to
+    /// decide when the reference is usable.
+    ///
+    /// Note that reading `Status0` resets its status bits, as with `get_full_device_status()`.
+    pub fn reference_loaded(&mut self) -> Result<bool, DeviceError<Spi::Error>> {
+        let status0 = self.device.status_0().read()?;
+        self.latch_power_down(status0.p_dwn());
+        Ok(status0.r_val())
+    }
+
+    /// Check if the reference counter overflowed by reading `OvfRef` from `Status0`.
+    /// An overflow indicates too many edges between two index pulses, which invalidates the
+    /// reference counter (REF) and the UPD register. Use this, e.g., in interpolation routines
+    /// to decide if a sample must be discarded.
+    ///
+    /// Note that reading `Status0` resets its status bits, as with `get_full_device_status()`.
+    pub fn reference_overflow(&mut self) -> Result<OverflowStatus, DeviceError<Spi::Error>> {
+        let status0 = self.device.status_0().read()?;
+        self.latch_power_down(status0.p_dwn());
+        Ok(status0.ovf_ref().into())
+    }
+
+    /// Read the reference counter (REF) and the UPD register together, e.g., for interpolation.
+    /// First, `Status0` is read to check the validity of both registers. The reference counter
+    /// is valid if it was loaded (`RVal`), the UPD register if it was loaded (`UpdVal`). If the
+    /// reference counter overflowed (`OvfRef`), both values are invalid. Only valid registers
+    /// are read, invalid ones are returned as `None`.
+    ///
+    /// Note that reading `Status0` resets its status bits, as with `get_full_device_status()`.
+    pub fn read_reference_and_upd(&mut self) -> Result<ReferenceAndUpd, DeviceError<Spi::Error>> {
+        let status0 = self.device.status_0().read()?;
+        self.latch_power_down(status0.p_dwn());
+        let mut values = ReferenceAndUpd::default();
+        if status0.ovf_ref() {
+            return Ok(values);
+        }
+        if status0.r_val() {
+            values.reference = Some(self.device.reference_counter().read()?.value());
+        }
+        if status0.upd_val() {
+            values.upd = Some(self.device.upd_register().read()?.value());
+        }
+        Ok(values)
+    }
+
+    /// Read the touch probe 1 (TP1) register.
+    /// First, `Status0` is read to get the touch probe status (`TpVal`), then the TP1 register.
+    /// The captured value is sign extended to the configured width of counter 0.
+    ///
+    /// Note that reading `Status0` resets its status bits, as with `get_full_device_status()`.
+    pub fn read_tp1(&mut self) -> Result<TouchProbeValue, DeviceError<Spi::Error>> {
+        self.read_tp(|device| Ok(device.touch_probe_1().read()?.value()))
+    }
+
+    /// Read the touch probe 2 (TP2) register.
+    /// First, `Status0` is read to get the touch probe status (`TpVal`), then the TP2 register.
+    /// The captured value is sign extended to the configured width of counter 0.
+    ///
+    /// Note that reading `Status0` resets its status bits, as with `get_full_device_status()`.
+    pub fn read_tp2(&mut self) -> Result<TouchProbeValue, DeviceError<Spi::Error>> {
+        self.read_tp(|device| Ok(device.touch_probe_2().read()?.value()))
     }
 
     /// Reset counters to zero.
     /// You can select which counters should be set to zero using the specific arguments.
+    /// The cached actuator pin status is written along, such that the actuator pins keep their
+    /// level.
     ///
     /// # Arguments
     /// * `cnt0`: If true, counter 0 is reset, else not.
@@ -254,16 +1183,30 @@ impl<Spi: SpiDevice> IcMd<Spi> {
         cnt1: bool,
         cnt2: bool,
     ) -> Result<(), DeviceError<Spi::Error>> {
-        let act0 = &self.actuator_status.act0;
-        let act1 = &self.actuator_status.act1;
-        self.device.instruction_byte().write(|reg| {
-            reg.set_ab_res_0(cnt0);
-            reg.set_ab_res_1(cnt1);
-            reg.set_ab_res_2(cnt2);
-            reg.set_act_0(act0.into());
-            reg.set_act_1(act1.into());
-        })?;
-        Ok(())
+        self.write_instruction(InstructionByteConfig {
+            reset_cnt0: cnt0,
+            reset_cnt1: cnt1,
+            reset_cnt2: cnt2,
+            ..InstructionByteConfig::from(self.actuator_status)
+        })
+    }
+
+    /// Reset counters to zero and read the counter value back.
+    /// This issues the same reset instruction as `reset_counters()`, preserving the actuator pin
+    /// status, and reads the counter right afterwards to confirm the reset.
+    ///
+    /// # Arguments
+    /// * `cnt0`: If true, counter 0 is reset, else not.
+    /// * `cnt1`: If true, counter 1 is reset, else not.
+    /// * `cnt2`: If true, counter 2 is reset, else not.
+    pub fn reset_and_read(
+        &mut self,
+        cnt0: bool,
+        cnt1: bool,
+        cnt2: bool,
+    ) -> Result<CntCount, IcMdError<Spi::Error>> {
+        self.reset_counters(cnt0, cnt1, cnt2)?;
+        self.read_counter()
     }
 
     /// Reset all counters.
@@ -273,35 +1216,310 @@ impl<Spi: SpiDevice> IcMd<Spi> {
         Ok(())
     }
 
+    /// Reset all counters, rewrite the configuration, and clear the status latches.
+    /// This is a "start fresh" operation: All counters are reset with `reset_all_counters()`,
+    /// the device is initialized again with `init()`, and the full device status is read to clear
+    /// the latched status bits. The status that was read, i.e., the status that was cleared, is
+    /// returned. As with `get_full_device_status()`, the cached device status is updated.
+    pub fn full_reset(&mut self) -> Result<FullDeviceStatus, IcMdError<Spi::Error>> {
+        self.reset_all_counters()?;
+        self.init()?;
+        Ok(self.get_full_device_status()?)
+    }
+
     /// Touch probe instruction
     /// Load touch probe 2 with touch probe 1 value and touch probe 1 wiht ABCNT value.
     pub fn touch_probe_instruction(&mut self) -> Result<(), DeviceError<Spi::Error>> {
-        let act0 = &self.actuator_status.act0;
-        let act1 = &self.actuator_status.act1;
+        self.write_instruction(InstructionByteConfig {
+            touch_probe: true,
+            ..InstructionByteConfig::from(self.actuator_status)
+        })
+    }
+
+    /// Get a builder to chain several instructions and write them in a single transaction.
+    /// The actuator pins keep their cached levels unless changed with the builder. See
+    /// `InstructionBuilder` for the available instructions.
+    pub fn instructions(&mut self) -> InstructionBuilder<'_, Spi> {
+        InstructionBuilder::new(self)
+    }
+
+    /// Write all bits of the instruction byte in a single transaction.
+    /// This allows to combine several instructions, e.g., to reset counter 0 and load the touch
+    /// probe registers at the same time. The cached actuator pin status is updated with the
+    /// actuator pin levels of the given configuration, the recorded pin modes are kept.
+    ///
+    /// # Arguments
+    /// * `config`: The instruction byte configuration to write.
+    pub fn write_instruction(
+        &mut self,
+        config: InstructionByteConfig,
+    ) -> Result<(), DeviceError<Spi::Error>> {
         self.device.instruction_byte().write(|reg| {
-            reg.set_tp(true);
-            reg.set_act_0(act0.into());
-            reg.set_act_1(act1.into());
+            reg.set_ab_res_0(config.reset_cnt0);
+            reg.set_ab_res_1(config.reset_cnt1);
+            reg.set_ab_res_2(config.reset_cnt2);
+            reg.set_zc_en(config.zero_codification);
+            reg.set_tp(config.touch_probe);
+            reg.set_act_0((&config.act0).into());
+            reg.set_act_1((&config.act1).into());
         })?;
+        self.actuator_status.act0 = config.act0;
+        self.actuator_status.act1 = config.act1;
+        self.last_instruction = config.into();
         Ok(())
     }
 
+    /// Get the instruction byte (0x30) that was last written by the driver.
+    /// The instruction byte is write only on the device, so this is the value cached by the
+    /// driver, e.g., for debugging. Before the first write, `0x00` is returned.
+    pub fn last_instruction_byte(&self) -> u8 {
+        self.last_instruction
+    }
+
+    /// Set the counting direction of a single counter.
+    /// Only the configuration register is rewritten, all other counter settings are preserved.
+    /// The cached counter configuration is updated accordingly if the write succeeds.
+    ///
+    /// # Arguments
+    /// * `channel`: The counter channel (0, 1, or 2) to change.
+    /// * `direction`: The new counting direction of this counter.
+    pub fn set_direction(
+        &mut self,
+        channel: u8,
+        direction: CntDirection,
+    ) -> Result<(), IcMdError<Spi::Error>> {
+        let mut config = self.counter_config;
+        config
+            .setup_mut(channel)
+            .ok_or(IcMdError::InvalidChannel(channel))?
+            .set_count_direction(direction);
+        self.write_counter_config(config)
+    }
+
+    /// Set the Z signal setup of a single counter.
+    /// Only the configuration register is rewritten, all other counter settings are preserved.
+    /// The cached counter configuration is updated accordingly if the write succeeds.
+    /// In the three counter configuration, Z signals are not available and an error is returned.
+    ///
+    /// # Arguments
+    /// * `channel`: The counter channel (0 or 1) to change.
+    /// * `z_signal`: The new Z signal setup of this counter.
+    pub fn set_z_signal(
+        &mut self,
+        channel: u8,
+        z_signal: CntZSignal,
+    ) -> Result<(), IcMdError<Spi::Error>> {
+        if let CntCfg::Cnt3Bit16(_, _, _) = self.counter_config {
+            return Err(IcMdError::ZSignalNotSupported);
+        }
+        let mut config = self.counter_config;
+        config
+            .setup_mut(channel)
+            .ok_or(IcMdError::InvalidChannel(channel))?
+            .set_z_signal(z_signal);
+        self.write_counter_config(config)
+    }
+
+    /// Read the configuration registers back from the device.
+    /// The counter configuration (0x00) and the operation mode (0x01) are read in a single
+    /// transaction and returned as raw bytes, e.g., to capture the state of a device for
+    /// diagnostics. The cached configuration of the driver is not changed.
+    pub fn snapshot_config(&mut self) -> Result<ConfigSnapshot, DeviceError<Spi::Error>> {
+        let mut raw = [0u8; 2];
+        self.device.interface.read_register(0x00, 16, &mut raw)?;
+        Ok(ConfigSnapshot {
+            counter_config: raw[0],
+            operation_mode: raw[1],
+        })
+    }
+
+    /// Read the readable registers of the device as `(address, value)` pairs, e.g., for a
+    /// register inspection tool during bring-up.
+    /// The counter configuration (0x00), the operation mode (0x01), the reference counter
+    /// (0x10), and the three status registers (0x48 to 0x4A) are read and returned in this order
+    /// without any decoding. The counter and touch probe registers are not included. The cached
+    /// configuration of the driver is not changed.
+    ///
+    /// Note that reading the status registers resets their status bits, as with
+    /// `get_full_device_status()`.
+    pub fn dump_registers(&mut self) -> Result<[(u8, u32); 6], DeviceError<Spi::Error>> {
+        let config = self.snapshot_config()?;
+        let reference = self.device.reference_counter().read()?.value();
+        let status = self.read_raw_status()?;
+        Ok([
+            (0x00, config.counter_config as u32),
+            (0x01, config.operation_mode as u32),
+            // The raw 24 bit register value, without the sign extension of the accessor.
+            (0x10, reference as u32 & 0x00FF_FFFF),
+            (0x48, status[0] as u32),
+            (0x49, status[1] as u32),
+            (0x4A, status[2] as u32),
+        ])
+    }
+
+    /// Write a configuration snapshot back to the device.
+    /// Both registers are written in a single transaction, as in `init()`, and the cached
+    /// configuration is updated accordingly. If the operation mode byte of the snapshot is
+    /// invalid, an error is returned without writing to the device.
+    ///
+    /// # Arguments
+    /// * `snapshot`: The configuration snapshot to restore.
+    pub fn restore_config(
+        &mut self,
+        snapshot: &ConfigSnapshot,
+    ) -> Result<(), IcMdError<Spi::Error>> {
+        let operation_mode = OperationMode::try_from(snapshot.operation_mode)?;
+        let raw = [snapshot.counter_config, snapshot.operation_mode];
+        self.device.interface.write_register(0x00, 16, &raw)?;
+        self.counter_config = CntCfg::from(snapshot.counter_config);
+        self.operation_mode = operation_mode;
+        Ok(())
+    }
+
+    /// Get the number of bytes that a counter read needs with the cached counter configuration,
+    /// e.g., to size your own DMA buffers. The address byte is not included.
+    /// See also `CntCfg::read_len()`.
+    pub const fn counter_read_len(&self) -> usize {
+        self.counter_config.read_len()
+    }
+
+    /// Get the cached counter configuration.
+    pub fn get_counter_config(&self) -> CntCfg {
+        self.counter_config
+    }
+
+    /// Get the counting direction of a single counter from the cached counter configuration,
+    /// e.g., to display it. No SPI communication takes place.
+    /// Returns `None` if the channel is not configured.
+    ///
+    /// # Arguments
+    /// * `channel`: The counter channel (0, 1, or 2).
+    pub fn get_direction(&self, channel: u8) -> Option<CntDirection> {
+        self.counter_config
+            .setup(channel)
+            .map(|setup| setup.count_direction())
+    }
+
     /// Set the counter configuration.
     /// This should be done prior to calling `init()`.
     pub fn set_counter_config(&mut self, config: CntCfg) {
         self.counter_config = config;
     }
 
+    /// Set the counter configuration and initialize the device with it.
+    /// This combines `set_counter_config()` and `init()` in one call. The cached configuration
+    /// is set even if the initialization fails.
+    ///
+    /// # Arguments
+    /// * `config`: The new counter configuration.
+    pub fn reconfigure(&mut self, config: CntCfg) -> Result<(), IcMdError<Spi::Error>> {
+        self.set_counter_config(config);
+        self.init()
+    }
+
+    /// Apply a counter configuration profile.
+    /// The counter configuration of the profile with the given index is set and the device
+    /// initialized with it.
+    ///
+    /// # Arguments
+    /// * `profiles`: The configuration profiles to choose from.
+    /// * `index`: The index of the profile to apply.
+    pub fn apply_profile<const N: usize>(
+        &mut self,
+        profiles: &ConfigProfiles<N>,
+        index: usize,
+    ) -> Result<(), IcMdError<Spi::Error>> {
+        let config = profiles
+            .get(index)
+            .ok_or(IcMdError::InvalidProfile(index))?;
+        self.reconfigure(config)
+    }
+
+    /// Get the cached operation mode.
+    pub fn get_operation_mode(&self) -> OperationMode {
+        self.operation_mode
+    }
+
+    /// Set the operation mode.
+    /// This should be done prior to calling `init()`.
+    pub fn set_operation_mode(&mut self, mode: OperationMode) {
+        self.operation_mode = mode;
+    }
+
+    /// Write the given counter configuration to the device and cache it if successful.
+    fn write_counter_config(&mut self, config: CntCfg) -> Result<(), IcMdError<Spi::Error>> {
+        self.device
+            .counter_configuration()
+            .write(|reg| reg.set_value(config.into()))?;
+        self.counter_config = config;
+        Ok(())
+    }
+
+    /// Read `Status0` and then the raw value of a touch probe register with the given function.
+    /// The value is sign extended to the configured width of counter 0.
+    fn read_tp<F>(&mut self, read_raw: F) -> Result<TouchProbeValue, DeviceError<Spi::Error>>
+    where
+        F: FnOnce(&mut Device<DeviceInterface<Spi>>) -> Result<u64, DeviceError<Spi::Error>>,
+    {
+        let status0 = self.device.status_0().read()?;
+        self.latch_power_down(status0.p_dwn());
+        let status = status0.tp_val().into();
+        let raw = read_raw(&mut self.device)?;
+        Ok(TouchProbeValue {
+            value: self.sign_extend_cnt0(raw),
+            status,
+        })
+    }
+
+    /// Sign extend a raw value to the configured width of counter 0.
+    fn sign_extend_cnt0(&self, raw: u64) -> i64 {
+        let bits = self.counter_config.channel_bits(0).unwrap_or(48);
+        motion::sign_extend(raw as i64, bits)
+    }
+
+    /// Read the counters and return the value of the given channel.
+    fn read_channel(&mut self, channel: u8) -> Result<i64, IcMdError<Spi::Error>> {
+        self.read_counter()?
+            .get_channel(channel)
+            .ok_or(IcMdError::InvalidChannel(channel))
+    }
+
+    /// Read the raw bytes of a counter read with the cached counter configuration.
+    fn read_frame(&mut self) -> Result<CounterFrame, DeviceError<Spi::Error>> {
+        self.read_frame_as(self.counter_config)
+    }
+
+    /// Read the raw bytes of a counter read with the given configuration.
+    /// The bytes are flipped into the byte order of the device if the byte order is overridden.
+    fn read_frame_as(&mut self, config: CntCfg) -> Result<CounterFrame, DeviceError<Spi::Error>> {
+        let mut frame = CounterFrame::new(config.read_len());
+        let raw = frame.as_bytes_mut();
+        self.device
+            .interface
+            .read_register(0x08, 8 * raw.len() as u32, raw)?;
+        if self.byte_order == ByteOrderOverride::LittleEndian {
+            raw.reverse();
+        }
+        Ok(frame)
+    }
+
+    /// Read all status registers and check if any counter reports an overflow.
+    fn read_any_overflow(&mut self) -> Result<bool, DeviceError<Spi::Error>> {
+        let status = self.device.status_all().read()?;
+        self.latch_power_down(status.p_dwn_0() || status.p_dwn_1() || status.p_dwn_2());
+        Ok(status.ovf_0() || status.ovf_1() || status.ovf_2())
+    }
+
+    /// Latch the `needs_reconfiguration` flag if a power down was observed in a status read.
+    fn latch_power_down(&mut self, p_dwn: bool) {
+        if p_dwn {
+            self.needs_reconfiguration = true;
+        }
+    }
+
     /// Set device status from two bools that were read and passed on to here.
     /// Note taat the inputs are from nerr and nwarn!
     fn set_device_status(&mut self, nwarn: bool, nerr: bool) {
-        self.device_status.warning = match nwarn {
-            true => WarningStatus::Ok,
-            false => WarningStatus::Warning,
-        };
-        self.device_status.error = match nerr {
-            true => ErrorStatus::Ok,
-            false => ErrorStatus::Error,
-        };
+        self.device_status = DeviceStatus::from_bits(nwarn, nerr);
     }
 }
@@ -0,0 +1,237 @@
+//! Module to hold helpers for motion calculations based on counter values
+
+use embedded_hal::spi::SpiDevice;
+
+use crate::IcMd;
+use crate::configs::CntCount;
+
+/// Compute the signed difference between two counter values of the given bit depth.
+///
+/// The counters of the iC-MD wrap around when they overflow. The difference is therefore
+/// computed modulo the bit depth of the counter and interpreted as a signed value, i.e., the
+/// shortest distance between the two counter values is returned.
+pub(crate) fn wrapped_delta(prev: i64, curr: i64, bits: u32) -> i64 {
+    sign_extend(curr.wrapping_sub(prev), bits)
+}
+
+/// Interpret the lowest `bits` bits of the given value as a two's complement signed value.
+pub(crate) fn sign_extend(value: i64, bits: u32) -> i64 {
+    let shift = 64 - bits;
+    (value << shift) >> shift
+}
+
+/// Estimate the velocity of a counter channel from two successive counter reads.
+///
+/// The velocity is returned in counts per second. Wrap-around of the counter between the two
+/// reads is handled, assuming that the counter moved less than half its range in between.
+///
+/// Returns `None` if the channel is not present in both reads, if the two reads stem from
+/// different counter configurations, or if `dt_micros` is zero.
+///
+/// # Arguments
+/// * `prev`: The previous counter read.
+/// * `curr`: The current counter read.
+/// * `dt_micros`: The time between the two reads in microseconds.
+/// * `channel`: The counter channel (0, 1, or 2) to compute the velocity for.
+pub fn velocity(prev: &CntCount, curr: &CntCount, dt_micros: u32, channel: u8) -> Option<f32> {
+    if dt_micros == 0 {
+        return None;
+    }
+    let delta = curr.delta_from(prev, channel)?;
+    Some(delta as f32 * 1_000_000.0 / dt_micros as f32)
+}
+
+/// Raw value of a single counter read.
+///
+/// The value is limited to the bit depth of the counter and wraps around when the counter
+/// overflows. To prevent mixing it up with an accumulated `Position`, there are no conversions
+/// between the two types:
+///
+/// ```compile_fail
+/// # use ic_md::{Position, RawCount};
+/// let position: Position = RawCount::new(42).into();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RawCount(i64);
+
+impl RawCount {
+    /// Create a new raw count from the given counter value.
+    pub const fn new(value: i64) -> Self {
+        Self(value)
+    }
+
+    /// Get the counter value.
+    pub const fn value(self) -> i64 {
+        self.0
+    }
+}
+
+/// Accumulated position of a counter.
+///
+/// Contrary to a `RawCount`, the position does not wrap around at the bit depth of the counter.
+/// There are no conversions between the two types:
+///
+/// ```compile_fail
+/// # use ic_md::{Position, RawCount};
+/// let raw: RawCount = Position::new(42).into();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Position(i64);
+
+impl Position {
+    /// Create a new position from the given value.
+    pub const fn new(value: i64) -> Self {
+        Self(value)
+    }
+
+    /// Get the position value.
+    pub const fn value(self) -> i64 {
+        self.0
+    }
+}
+
+/// Tracker that accumulates the position of a single counter channel over successive reads.
+///
+/// The counter values wrap around at the bit depth of the counter. The tracker adds up the
+/// wrap-corrected differences between successive reads, such that the tracked `Position` does
+/// not wrap. Reads must be frequent enough that the counter moves less than half its range in
+/// between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PositionTracker {
+    channel: u8,
+    last: Option<CntCount>,
+    position: Position,
+    last_delta: Option<i64>,
+}
+
+impl PositionTracker {
+    /// Create a new tracker for the given counter channel (0, 1, or 2).
+    /// The position is initialized with the counter value of the first update.
+    pub const fn new(channel: u8) -> Self {
+        Self {
+            channel,
+            last: None,
+            position: Position::new(0),
+            last_delta: None,
+        }
+    }
+
+    /// Update the tracker with a new counter read and return the accumulated position.
+    ///
+    /// Returns `None` and leaves the tracker unchanged if the channel is not present in the read
+    /// or if the read stems from a different counter configuration than the previous one.
+    ///
+    /// # Arguments
+    /// * `count`: The current counter read.
+    pub fn update(&mut self, count: &CntCount) -> Option<Position> {
+        match &self.last {
+            None => {
+                self.position = Position::new(count.get_channel(self.channel)?);
+            }
+            Some(prev) => {
+                let delta = count.delta_from(prev, self.channel)?;
+                self.position = Position::new(self.position.value() + delta);
+                self.last_delta = Some(delta);
+            }
+        }
+        self.last = Some(*count);
+        Some(self.position)
+    }
+
+    /// Get the accumulated position, `None` if the tracker was not updated yet.
+    pub fn position(&self) -> Option<Position> {
+        self.last.map(|_| self.position)
+    }
+
+    /// Get the difference between the last two updates, `None` if there were less than two.
+    pub fn last_delta(&self) -> Option<i64> {
+        self.last_delta
+    }
+
+    /// Check if the counter appears to be frozen.
+    ///
+    /// Returns `true` if motion is expected, but the counter did not change between the last two
+    /// updates, e.g., because of a broken encoder cable. Returns `false` otherwise, including if
+    /// there were less than two updates.
+    ///
+    /// # Arguments
+    /// * `expected_moving`: Whether the axis is expected to move, e.g., because it is driven.
+    pub fn is_stalled(&self, expected_moving: bool) -> bool {
+        expected_moving && self.last_delta == Some(0)
+    }
+}
+
+/// Helper to convert counter values to engineering units and back.
+///
+/// The scaler stores the number of counts per unit, e.g., counts per millimeter or counts per
+/// degree. For a rotary encoder, use `CountScaler::per_revolution` to create the scaler from the
+/// counts per revolution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CountScaler {
+    counts_per_unit: f32,
+}
+
+impl CountScaler {
+    /// Create a new scaler with the given number of counts per unit.
+    pub const fn new(counts_per_unit: f32) -> Self {
+        Self { counts_per_unit }
+    }
+
+    /// Create a new scaler for a rotary encoder.
+    ///
+    /// # Arguments
+    /// * `counts_per_rev`: The number of counts per revolution of the encoder.
+    /// * `units_per_rev`: The number of units per revolution, e.g., 360 for degrees.
+    pub fn per_revolution(counts_per_rev: u32, units_per_rev: f32) -> Self {
+        Self::new(counts_per_rev as f32 / units_per_rev)
+    }
+
+    /// Get the number of counts per unit.
+    pub fn counts_per_unit(&self) -> f32 {
+        self.counts_per_unit
+    }
+
+    /// Convert a counter value to units.
+    pub fn to_units(self, count: i64) -> f32 {
+        count as f32 / self.counts_per_unit
+    }
+
+    /// Convert a value in units to counts, rounded to the nearest count.
+    pub fn to_counts(self, units: f32) -> i64 {
+        let counts = units * self.counts_per_unit;
+        if counts >= 0.0 {
+            (counts + 0.5) as i64
+        } else {
+            (counts - 0.5) as i64
+        }
+    }
+}
+
+/// Generic interface for quadrature counters.
+///
+/// This trait allows higher-level crates, e.g., motion frameworks, to accept any quadrature
+/// counter driver. The trait is object safe, such that it can also be used as
+/// `&mut dyn QuadratureCounter`.
+pub trait QuadratureCounter {
+    /// Read the current position of the given counter channel.
+    ///
+    /// Returns `None` if the channel is not available or if the position could not be read.
+    fn read_position(&mut self, channel: u8) -> Option<i64>;
+}
+
+/// Reads all counters of the current configuration and returns the value of the given channel.
+/// If the channel is not configured, `None` is returned without reading from the device. Errors
+/// during the SPI communication are mapped to `None` as well, use `IcMd::read_counter()` to get
+/// them.
+impl<Spi: SpiDevice> QuadratureCounter for IcMd<Spi> {
+    fn read_position(&mut self, channel: u8) -> Option<i64> {
+        if !self.counter_config.has_channel(channel) {
+            return None;
+        }
+        self.read_counter().ok()?.get_channel(channel)
+    }
+}
@@ -0,0 +1,54 @@
+//! Module to hold the guard that leaves the device in a known state at the end of a session
+
+use embedded_hal::spi::{Error, SpiDevice};
+
+use crate::IcMd;
+use crate::error::IcMdError;
+
+/// Guard for a measurement session with the iC-MD.
+///
+/// On construction, the device is initialized with `IcMd::init()`. When the guard is dropped,
+/// e.g., on an early return, all counters are reset if `reset_on_drop` was set. This way, the
+/// device is always left in a known state. As `drop` cannot return errors, the kind of a failed
+/// reset is stored in the driver and can be inspected afterwards with
+/// `IcMd::session_error()`. The driver is accessible during the session via `icmd()`.
+#[derive(Debug)]
+pub struct IcMdSession<'a, Spi: SpiDevice> {
+    icmd: &'a mut IcMd<Spi>,
+    reset_on_drop: bool,
+}
+
+impl<'a, Spi: SpiDevice> IcMdSession<'a, Spi> {
+    /// Initialize the device and start a new session.
+    /// A previously stored session error is cleared.
+    ///
+    /// # Arguments
+    /// * `icmd`: The iC-MD driver.
+    /// * `reset_on_drop`: If true, all counters are reset when the session ends.
+    pub fn new(
+        icmd: &'a mut IcMd<Spi>,
+        reset_on_drop: bool,
+    ) -> Result<Self, IcMdError<Spi::Error>> {
+        icmd.session_error = None;
+        icmd.init()?;
+        Ok(Self {
+            icmd,
+            reset_on_drop,
+        })
+    }
+
+    /// Get a mutable reference to the driver.
+    pub fn icmd(&mut self) -> &mut IcMd<Spi> {
+        self.icmd
+    }
+}
+
+impl<Spi: SpiDevice> Drop for IcMdSession<'_, Spi> {
+    fn drop(&mut self) {
+        if self.reset_on_drop
+            && let Err(err) = self.icmd.reset_all_counters()
+        {
+            self.icmd.session_error = Some(err.0.kind());
+        }
+    }
+}
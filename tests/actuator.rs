@@ -0,0 +1,126 @@
+//! This file contains tests that show how to control the actuator pins ACT0 and ACT1.
+//!
+//! For your application, you will have to provide your own `SPIDevice` interface.
+
+use embedded_hal_mock::eh1::{
+    delay::{CheckedDelay, Transaction as DelayTransaction},
+    spi::{Mock, Transaction},
+};
+
+use ic_md::{ActuatorMode, ActuatorPin, IcMd, PinStatus};
+
+/// Pulse ACT0 while ACT1 stays high.
+#[test]
+fn test_pulse_actuator() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Set ACT1 high
+        Transaction::write(0x30),
+        Transaction::write(0x40),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Pulse ACT0 high, ACT1 stays high
+        Transaction::write(0x30),
+        Transaction::write(0x60),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Return ACT0 to low, ACT1 stays high
+        Transaction::write(0x30),
+        Transaction::write(0x40),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice and your delay provider
+    let mut spi_device = Mock::new(&expectations);
+    let mut delay = CheckedDelay::new(&[DelayTransaction::delay_us(500)]);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Set ACT1 to high, ACT0 remains low
+    icmd.configure_actuator_pins(&PinStatus::Low, &PinStatus::High)
+        .unwrap();
+
+    // Pulse ACT0 for 500 us
+    icmd.pulse_actuator(ActuatorPin::Act0, 500, &mut delay)
+        .unwrap();
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+    delay.done();
+}
+
+/// Record the mode of the actuator pins to check if they are active.
+#[test]
+fn test_actuator_pins_with_mode() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // ACT0 low, ACT1 high
+        Transaction::write(0x30),
+        Transaction::write(0x40),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // ACT0 is wired as open-drain, ACT1 as push-pull: Set both of them active
+    icmd.configure_actuator_pins_with_mode(
+        &PinStatus::Low,
+        ActuatorMode::OpenDrain,
+        &PinStatus::High,
+        ActuatorMode::PushPull,
+    )
+    .unwrap();
+
+    // The modes are stored alongside the pin status
+    let status = icmd.get_actuator_status();
+    assert_eq!(status.act0_mode, ActuatorMode::OpenDrain);
+    assert_eq!(status.act1_mode, ActuatorMode::PushPull);
+    assert!(status.is_active(ActuatorPin::Act0));
+    assert!(status.is_active(ActuatorPin::Act1));
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Drive two relays inversely with complementary actuator pins.
+#[test]
+fn test_actuators_complementary() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // ACT0 high, ACT1 low
+        Transaction::write(0x30),
+        Transaction::write(0x20),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // ACT0 low, ACT1 high
+        Transaction::write(0x30),
+        Transaction::write(0x40),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Exactly one actuator pin is high after each write.
+    icmd.set_actuators_complementary(PinStatus::High).unwrap();
+    let status = icmd.get_actuator_status();
+    assert_eq!(
+        (status.act0, status.act1),
+        (PinStatus::High, PinStatus::Low)
+    );
+
+    icmd.set_actuators_complementary(PinStatus::Low).unwrap();
+    let status = icmd.get_actuator_status();
+    assert_eq!(
+        (status.act0, status.act1),
+        (PinStatus::Low, PinStatus::High)
+    );
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
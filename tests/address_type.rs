@@ -0,0 +1,103 @@
+//! This file checks the register address type of the low-level device interface.
+//!
+//! The iC-MD uses `u8` addresses, which is the default. Device variants with a different
+//! addressing scheme can implement `RegisterAddress` for their own address type.
+
+use device_driver::RegisterInterface;
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+use ic_md::dd::{DeviceInterface, MAX_ADDRESS_LEN, RegisterAddress, WriteMode, read_address};
+
+/// A 16 bit address with the read bit in the highest bit - testing only.
+#[derive(Debug, Clone, Copy)]
+struct WideAddress(u16);
+
+impl RegisterAddress for WideAddress {
+    fn encode(self, read: bool, buf: &mut [u8; MAX_ADDRESS_LEN]) -> usize {
+        let address = match read {
+            true => 0x8000 | self.0,
+            false => self.0,
+        };
+        buf[..2].copy_from_slice(&address.to_be_bytes());
+        2
+    }
+}
+
+/// The default `u8` addresses are sent as before, with the read bit set for reads.
+#[test]
+fn test_u8_address() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Write 0x30
+        Transaction::write(0x30),
+        Transaction::write(0x07),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Read 0x48
+        Transaction::write(0x80 | 0x48),
+        Transaction::read(0x01),
+        Transaction::transaction_end(),
+    ];
+    let mut spi_device = Mock::new(&expectations);
+
+    let mut interface: DeviceInterface<_> = DeviceInterface::new(&mut spi_device);
+    interface.write_register(0x30, 8, &[0x07]).unwrap();
+    let mut data = [0u8; 1];
+    interface.read_register(0x48, 8, &mut data).unwrap();
+    assert_eq!(data, [0x01]);
+
+    spi_device.done();
+}
+
+/// A wider address type is encoded by its `RegisterAddress` implementation.
+#[test]
+fn test_wide_address() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Write 0x0123
+        Transaction::write_vec(vec![0x01, 0x23]),
+        Transaction::write(0xAB),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Contiguous write of 0x0123
+        Transaction::write_vec(vec![0x01, 0x23, 0xCD]),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Read 0x0123
+        Transaction::write_vec(vec![0x81, 0x23]),
+        Transaction::read(0xEF),
+        Transaction::transaction_end(),
+    ];
+    let mut spi_device = Mock::new(&expectations);
+
+    let mut interface =
+        DeviceInterface::<_, WideAddress>::new_with_address_type(&mut spi_device, WriteMode::Split);
+    interface
+        .write_register(WideAddress(0x0123), 8, &[0xAB])
+        .unwrap();
+
+    let mut interface = DeviceInterface::<_, WideAddress>::new_with_address_type(
+        &mut spi_device,
+        WriteMode::Contiguous,
+    );
+    interface
+        .write_register(WideAddress(0x0123), 8, &[0xCD])
+        .unwrap();
+    let mut data = [0u8; 1];
+    interface
+        .read_register(WideAddress(0x0123), 8, &mut data)
+        .unwrap();
+    assert_eq!(data, [0xEF]);
+
+    spi_device.done();
+}
+
+/// The read address sets the highest bit of the register address.
+#[test]
+fn test_read_address() {
+    assert_eq!(read_address(0x00), 0x80);
+    assert_eq!(read_address(0x08), 0x80 | 0x08);
+    assert_eq!(read_address(0x48), 0xC8);
+
+    // The encoded `u8` read address is the same.
+    let mut buf = [0u8; MAX_ADDRESS_LEN];
+    assert_eq!(0x48u8.encode(true, &mut buf), 1);
+    assert_eq!(buf[0], read_address(0x48));
+}
@@ -0,0 +1,47 @@
+//! This file contains tests that show how to check the NWARN and NERR pins via GPIO inputs.
+//!
+//! For your application, you will have to provide your own `SPIDevice` and `InputPin`s.
+
+use embedded_hal_mock::eh1::{
+    digital::{Mock as PinMock, State as PinState, Transaction as PinTransaction},
+    spi::Mock,
+};
+
+use ic_md::{ErrorStatus, IcMd, IcMdWithAlerts, WarningStatus};
+
+/// Check the alert pins for warnings and errors without SPI communication.
+#[test]
+fn test_alerts_from_pins() {
+    // Pin transactions - ignore this if you look for the example
+    let warn_expectations = [
+        PinTransaction::get(PinState::High), // No warning
+        PinTransaction::get(PinState::Low),  // Warning
+    ];
+    let err_expectations = [
+        PinTransaction::get(PinState::High), // No error
+        PinTransaction::get(PinState::High), // No error
+    ];
+
+    // Initialize your SPIDevice and the input pins
+    let mut spi_device = Mock::<u8>::new(&[]);
+    let warn_pin = PinMock::new(&warn_expectations);
+    let err_pin = PinMock::new(&err_expectations);
+
+    // Wrap the counter with the default setup together with the alert pins
+    let icmd = IcMd::new(&mut spi_device);
+    let mut icmd = IcMdWithAlerts::new(icmd, warn_pin, err_pin);
+
+    // Both pins are high, so everything is ok.
+    assert!(icmd.alerts().unwrap().is_ok());
+
+    // NWARN is pulled low by the device, which signals a warning.
+    let status = icmd.alerts().unwrap();
+    assert_eq!(status.get_warning(), WarningStatus::Warning);
+    assert_eq!(status.get_error(), ErrorStatus::Ok);
+
+    // Check that all our expectations are met - testing only
+    let (_, mut warn_pin, mut err_pin) = icmd.release();
+    warn_pin.done();
+    err_pin.done();
+    spi_device.done();
+}
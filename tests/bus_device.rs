@@ -0,0 +1,53 @@
+//! This file contains a test that shows how to use the iC-MD with an `SpiBus` and a manually
+//! driven chip select pin.
+//!
+//! The driver frames every transaction by pulling the chip select pin low before and high after
+//! it. This requires the `embedded-hal-bus` feature. For your application, you will have to
+//! provide your own `SpiBus` and `OutputPin`.
+
+use embedded_hal_mock::eh1::{
+    digital::{Mock as PinMock, State as PinState, Transaction as PinTransaction},
+    spi::{Mock as SpiMock, Transaction},
+};
+
+use ic_md::IcMd;
+
+/// Initialize the counter and read it via an `SpiBus` and a chip select pin.
+#[test]
+fn test_from_bus() {
+    // SPI bus transactions - ignore this if you look for the example
+    let bus_expectations = [
+        Transaction::write(0x00), // Initialization
+        Transaction::write_vec(vec![0x02, 0x00]),
+        Transaction::flush(),
+        Transaction::write(0x80 | 0x08), // Read the counter
+        Transaction::read_vec(vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x2A, 0xC0]),
+        Transaction::flush(),
+    ];
+
+    // Chip select transactions: set high on creation, then low and high for each transaction
+    let cs_expectations = [
+        PinTransaction::set(PinState::High),
+        PinTransaction::set(PinState::Low),
+        PinTransaction::set(PinState::High),
+        PinTransaction::set(PinState::Low),
+        PinTransaction::set(PinState::High),
+    ];
+
+    // Initialize your SPI bus and the chip select pin
+    let mut bus = SpiMock::new(&bus_expectations);
+    let mut cs = PinMock::new(&cs_expectations);
+
+    // Get a handle to the counter with the default setup from the bus and the pin
+    let mut icmd = IcMd::from_bus(&mut bus, &mut cs).unwrap();
+
+    // Initialize the counter and read it, the transactions are framed by the chip select pin
+    icmd.init().unwrap();
+    let counter_value = icmd.read_counter().unwrap();
+    assert_eq!(counter_value.get_cnt0(), Some(42));
+    assert!(icmd.get_device_status().is_ok());
+
+    // Check that all our expectations are met - testing only
+    bus.done();
+    cs.done();
+}
@@ -4,7 +4,7 @@
 
 use embedded_hal_mock::eh1::spi::{Mock, Transaction};
 
-use ic_md::IcMd;
+use ic_md::{CntCfg, CntDirection, CntSetup, CntZSignal, ConfigError, IcMd, ParseError};
 
 /// Setup a standard counter and query its test status.
 #[test]
@@ -13,7 +13,7 @@ fn test_default_icmd_and_counter_read() {
     let expectations = [
         Transaction::transaction_start(), // Initialization
         Transaction::write(0x00),
-        Transaction::write(0x4E),
+        Transaction::write_vec(vec![0x4E, 0x80]),
         Transaction::transaction_end(),
         Transaction::transaction_start(), // Read the counter
         Transaction::write(0x80 | 0x08),
@@ -38,6 +38,8 @@ fn test_default_icmd_and_counter_read() {
 
     // Now we set our counter configuration to the iC-MD device and initialize it.
     icmd.set_counter_config(counter_setup);
+    // Configurations with more than one counter only support TTL inputs.
+    icmd.set_operation_mode(ic_md::OperationMode::new().with_input_type(ic_md::InputType::Ttl));
     icmd.init().unwrap();
 
     // Read out the counter
@@ -66,3 +68,373 @@ fn test_default_icmd_and_counter_read() {
 
     spi_device.done(); // Ensure all transactions were executed
 }
+
+/// Change the counting direction of a counter at runtime without a full re-initialization.
+#[test]
+fn test_set_direction_at_runtime() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Initialization
+        Transaction::write(0x00),
+        Transaction::write_vec(vec![0x27, 0x80]),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Counter 0 set to CCW
+        Transaction::write(0x00),
+        Transaction::write(0x2F),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter and set up three 16 bit counters, counter 2 counting CCW.
+    let mut icmd = IcMd::new(&mut spi_device);
+    let cnt2_setup = ic_md::CntSetup::new(ic_md::CntDirection::CCW, ic_md::CntZSignal::Normal);
+    icmd.set_counter_config(ic_md::CntCfg::Cnt3Bit16(
+        ic_md::CntSetup::default(),
+        ic_md::CntSetup::default(),
+        cnt2_setup,
+    ));
+    // Configurations with more than one counter only support TTL inputs.
+    icmd.set_operation_mode(ic_md::OperationMode::new().with_input_type(ic_md::InputType::Ttl));
+    icmd.init().unwrap();
+
+    // Flip counter 0 to count counterclockwise. The direction of counter 2 is preserved.
+    icmd.set_direction(0, ic_md::CntDirection::CCW).unwrap();
+
+    // Counter 3 does not exist, so trying to change its direction returns an error.
+    assert_eq!(
+        icmd.set_direction(3, ic_md::CntDirection::CCW),
+        Err(ic_md::IcMdError::InvalidChannel(3))
+    );
+
+    spi_device.done(); // Ensure all transactions were executed
+}
+
+/// The driver uses the default configuration if no other configuration is set.
+#[test]
+fn test_default_config() {
+    // No SPI transactions are expected, we only create the driver.
+    let mut spi_device = Mock::new(&[]);
+
+    let icmd = IcMd::new(&mut spi_device);
+
+    // The default configuration is available as a constant.
+    assert_eq!(
+        icmd.get_counter_config(),
+        IcMd::<&mut Mock<u8>>::DEFAULT_CONFIG
+    );
+
+    spi_device.done(); // Ensure all transactions were executed
+}
+
+/// Change the Z signal setup of a counter at runtime.
+#[test]
+fn test_set_z_signal_at_runtime() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Initialization
+        Transaction::write(0x00),
+        Transaction::write_vec(vec![0x06, 0x80]),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Counter 1 Z signal inverted
+        Transaction::write(0x00),
+        Transaction::write(0x86),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Initialization with three counters
+        Transaction::write(0x00),
+        Transaction::write_vec(vec![0x07, 0x80]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter and set up two 16 bit counters.
+    let mut icmd = IcMd::new(&mut spi_device);
+    icmd.set_counter_config(ic_md::CntCfg::Cnt2Bit16(
+        ic_md::CntSetup::default(),
+        ic_md::CntSetup::default(),
+    ));
+    // Configurations with more than one counter only support TTL inputs.
+    icmd.set_operation_mode(ic_md::OperationMode::new().with_input_type(ic_md::InputType::Ttl));
+    icmd.init().unwrap();
+
+    // Invert the Z signal of counter 1.
+    icmd.set_z_signal(1, ic_md::CntZSignal::Inverted).unwrap();
+
+    // In the three counter configuration, the Z signals cannot be configured.
+    icmd.set_counter_config(ic_md::CntCfg::Cnt3Bit16(
+        ic_md::CntSetup::default(),
+        ic_md::CntSetup::default(),
+        ic_md::CntSetup::default(),
+    ));
+    icmd.init().unwrap();
+    assert_eq!(
+        icmd.set_z_signal(0, ic_md::CntZSignal::Inverted),
+        Err(ic_md::IcMdError::ZSignalNotSupported)
+    );
+
+    spi_device.done(); // Ensure all transactions were executed
+}
+
+/// Put the device back to its power-on configuration.
+#[test]
+fn test_reset_to_defaults() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Counter configuration and operation mode reset values
+        Transaction::write(0x00),
+        Transaction::write_vec(vec![0x00, 0x00]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter and set up a non-default configuration.
+    let mut icmd = IcMd::new(&mut spi_device);
+    icmd.set_counter_config(ic_md::CntCfg::Cnt2Bit16(
+        ic_md::CntSetup::default(),
+        ic_md::CntSetup::default(),
+    ));
+
+    // Reset to the power-on defaults, which also updates the cached configuration.
+    icmd.reset_to_defaults().unwrap();
+    assert_eq!(
+        icmd.get_counter_config(),
+        IcMd::<&mut Mock<u8>>::POWER_ON_CONFIG
+    );
+
+    spi_device.done(); // Ensure all transactions were executed
+}
+
+/// Swap between two stored configuration profiles at runtime.
+#[test]
+fn test_apply_config_profiles() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Profile 1: Two 24 bit counters
+        Transaction::write(0x00),
+        Transaction::write_vec(vec![0x01, 0x80]),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Profile 0: One 48 bit counter
+        Transaction::write(0x00),
+        Transaction::write_vec(vec![0x02, 0x80]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Store two profiles: a single 48 bit counter and two 24 bit counters.
+    let profiles = ic_md::ConfigProfiles::new([
+        ic_md::CntCfg::Cnt1Bit48(ic_md::CntSetup::default()),
+        ic_md::CntCfg::Cnt2Bit24(ic_md::CntSetup::default(), ic_md::CntSetup::default()),
+    ]);
+
+    // The profiles share the operation mode. Two counters only support TTL inputs.
+    icmd.set_operation_mode(ic_md::OperationMode::new().with_input_type(ic_md::InputType::Ttl));
+
+    // Apply the second profile, then switch back to the first one.
+    icmd.apply_profile(&profiles, 1).unwrap();
+    assert_eq!(icmd.get_counter_config(), profiles.get(1).unwrap());
+    icmd.apply_profile(&profiles, 0).unwrap();
+    assert_eq!(icmd.get_counter_config(), profiles.get(0).unwrap());
+
+    // A profile index that is out of range returns an error without writing to the device.
+    assert_eq!(
+        icmd.apply_profile(&profiles, 2),
+        Err(ic_md::IcMdError::InvalidProfile(2))
+    );
+
+    spi_device.done(); // Ensure all transactions were executed
+}
+
+/// Read the raw counter configuration byte back from the device.
+#[test]
+fn test_read_config_byte() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::write(0x80), // Read from 0x00
+        Transaction::read(0x2A),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // The byte is returned exactly as the device reports it.
+    assert_eq!(icmd.read_config_byte().unwrap(), 0x2A);
+
+    spi_device.done(); // Ensure all transactions were executed
+}
+
+/// Validate a three counter configuration with an inverted Z signal, which is ignored.
+#[test]
+fn test_validate_three_counters_z_signal() {
+    // The default setups are fine, as no Z signal is configured.
+    let config = CntCfg::Cnt3Bit16(
+        CntSetup::default(),
+        CntSetup::default(),
+        CntSetup::default(),
+    );
+    assert_eq!(config.validate(), Ok(()));
+
+    // An inverted Z signal on counter 1 would not take effect, so validation fails.
+    let inverted = CntSetup::new(CntDirection::CW, CntZSignal::Inverted);
+    let config = CntCfg::Cnt3Bit16(CntSetup::default(), inverted, CntSetup::default());
+    assert_eq!(config.validate(), Err(ConfigError::ZSignalIgnored(1)));
+
+    // In the two counter configurations, the Z signal is used.
+    let config = CntCfg::Cnt2Bit16(CntSetup::default(), inverted);
+    assert_eq!(config.validate(), Ok(()));
+}
+
+/// Write the registers with the address and the data in a single buffer.
+#[test]
+fn test_contiguous_write_mode() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Address 0x00, counter config, and operation mode
+        Transaction::write_vec(vec![0x00, 0x02, 0x00]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup, writing contiguous buffers
+    let mut icmd = IcMd::new_with_write_mode(&mut spi_device, ic_md::dd::WriteMode::Contiguous);
+
+    // Initialize the counter, which writes the address and both registers in one operation
+    icmd.init().unwrap();
+
+    spi_device.done(); // Ensure all transactions were executed
+}
+
+/// Reconfigure the counter and initialize the device in a single call.
+#[test]
+fn test_reconfigure() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Initialization with one 48 bit counter
+        Transaction::write(0x00),
+        Transaction::write_vec(vec![0x02, 0x00]),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Reconfigure to two 16 bit counters with TTL inputs
+        Transaction::write(0x00),
+        Transaction::write_vec(vec![0x06, 0x80]),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Read the two counters
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(vec![0xFF, 0xFF, 0x00, 0x2A, 0xC0]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup and initialize it
+    let mut icmd = IcMd::new(&mut spi_device);
+    icmd.init().unwrap();
+
+    // Switch to two 16 bit counters with TTL inputs, no separate call to `init()` is required.
+    icmd.set_operation_mode(ic_md::OperationMode::new().with_input_type(ic_md::InputType::Ttl));
+    let config = CntCfg::Cnt2Bit16(CntSetup::default(), CntSetup::default());
+    icmd.reconfigure(config).unwrap();
+    assert_eq!(icmd.get_counter_config(), config);
+
+    // The counters are read with the new configuration.
+    let counter_value = icmd.read_counter().unwrap();
+    assert!(counter_value.matches(&[42, -1]));
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Get the configured counting direction of each counter, e.g., to display it.
+#[test]
+fn test_get_direction() {
+    // No SPI transactions are required, only the cached configuration is used.
+    let mut spi_device = Mock::<u8>::new(&[]);
+
+    // The default configuration has one counter counting clockwise.
+    let mut icmd = IcMd::new(&mut spi_device);
+    assert_eq!(icmd.get_direction(0), Some(CntDirection::CW));
+    assert_eq!(icmd.get_direction(1), None);
+
+    // Two counters with different directions.
+    icmd.set_counter_config(CntCfg::Cnt2Bit24(
+        CntSetup::new(CntDirection::CW, CntZSignal::Normal),
+        CntSetup::new(CntDirection::CCW, CntZSignal::Normal),
+    ));
+    assert_eq!(icmd.get_direction(0), Some(CntDirection::CW));
+    assert_eq!(icmd.get_direction(1), Some(CntDirection::CCW));
+    assert_eq!(icmd.get_direction(2), None);
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Parse the counter configuration from a string, e.g., from a config file or a command line.
+#[test]
+fn test_parse_counter_config() {
+    let setup = CntSetup::default();
+    let expected = [
+        ("1x24", CntCfg::Cnt1Bit24(setup)),
+        ("2x24", CntCfg::Cnt2Bit24(setup, setup)),
+        ("1x48", CntCfg::Cnt1Bit48(setup)),
+        ("1x16", CntCfg::Cnt1Bit16(setup)),
+        ("1x32", CntCfg::Cnt1Bit32(setup)),
+        ("32+16", CntCfg::Cnt2Bit32Bit16(setup, setup)),
+        ("2x16", CntCfg::Cnt2Bit16(setup, setup)),
+        ("3x16", CntCfg::Cnt3Bit16(setup, setup, setup)),
+    ];
+    for (name, config) in expected {
+        assert_eq!(name.parse::<CntCfg>(), Ok(config), "{name}");
+    }
+
+    // Whitespace and the case of the `x` do not matter.
+    assert_eq!(" 2X24\n".parse(), Ok(CntCfg::Cnt2Bit24(setup, setup)));
+
+    // There is no configuration with two 48-bit counters.
+    assert_eq!(
+        "2x48".parse::<CntCfg>(),
+        Err(ParseError::UnknownCounterConfig)
+    );
+}
+
+/// Write the counting direction and the Z signal setup to a string and parse them back.
+#[test]
+fn test_parse_direction_and_z_signal() {
+    for direction in [CntDirection::CW, CntDirection::CCW] {
+        assert_eq!(direction.to_string().parse(), Ok(direction));
+    }
+    for z_signal in [CntZSignal::Normal, CntZSignal::Inverted] {
+        assert_eq!(z_signal.to_string().parse(), Ok(z_signal));
+    }
+
+    // The names are lowercase, but parsed case-insensitive.
+    assert_eq!(CntDirection::CCW.to_string(), "ccw");
+    assert_eq!(CntZSignal::Inverted.to_string(), "inverted");
+    assert_eq!("CW".parse(), Ok(CntDirection::CW));
+    assert_eq!(" Inverted ".parse(), Ok(CntZSignal::Inverted));
+
+    // Unknown names are rejected.
+    assert_eq!(
+        "clockwise".parse::<CntDirection>(),
+        Err(ParseError::UnknownDirection)
+    );
+    assert_eq!(
+        "high".parse::<CntZSignal>(),
+        Err(ParseError::UnknownZSignal)
+    );
+}
@@ -0,0 +1,107 @@
+//! This file contains tests that show how to work with the counter values returned by the iC-MD.
+//!
+//! The counter values are only processed here, so no `SPIDevice` is needed.
+
+use ic_md::{CntCount, CountError, Position, RawCount};
+
+/// Get all counter values as an array to iterate over them.
+#[test]
+fn test_counter_values_as_array() {
+    // A counter value of a two counter configuration
+    let counter_value = CntCount::Cnt2Bit16(13, -42);
+
+    // The array contains the configured counters in channel order, counter 2 is not configured.
+    assert_eq!(counter_value.as_array(), [Some(13), Some(-42), None]);
+}
+
+/// Compare counter values without calling the individual getters.
+#[test]
+fn test_counter_values_matches() {
+    let counter_value = CntCount::Cnt2Bit16(13, -42);
+
+    // Compare against the values of all configured counters in channel order.
+    assert!(counter_value.matches(&[13, -42]));
+
+    // Different values or a different number of counters do not match.
+    assert!(!counter_value.matches(&[-42, 13]));
+    assert!(!counter_value.matches(&[13]));
+    assert!(!counter_value.matches(&[13, -42, 0]));
+
+    // Counter values can also be compared directly with each other.
+    assert_eq!(counter_value, CntCount::Cnt2Bit16(13, -42));
+}
+
+/// Compute the wrap-corrected difference of 16 and 24 bit counters.
+#[test]
+fn test_delta_from_wrap_around() {
+    // A 16 bit counter that overflowed forward and one that underflowed backward.
+    let prev = CntCount::Cnt1Bit16(32_760);
+    let curr = CntCount::Cnt1Bit16(-32_766);
+    assert_eq!(curr.delta_from(&prev, 0), Some(10));
+    assert_eq!(prev.delta_from(&curr, 0), Some(-10));
+
+    // The same for counter 1 of a two 24 bit counter configuration.
+    let prev = CntCount::Cnt2Bit24(0, 8_388_600);
+    let curr = CntCount::Cnt2Bit24(5, -8_388_604);
+    assert_eq!(curr.delta_from(&prev, 0), Some(5));
+    assert_eq!(curr.delta_from(&prev, 1), Some(12));
+    assert_eq!(prev.delta_from(&curr, 1), Some(-12));
+
+    // Counter 2 does not exist in this configuration.
+    assert_eq!(curr.delta_from(&prev, 2), None);
+}
+
+/// Reads from different counter configurations cannot be compared.
+#[test]
+fn test_delta_from_mismatched_configs() {
+    let prev = CntCount::Cnt1Bit24(0);
+    let curr = CntCount::Cnt2Bit24(10, 0);
+    assert_eq!(curr.delta_from(&prev, 0), None);
+}
+
+/// Raw counts and positions are distinct types that are only converted explicitly.
+#[test]
+fn test_raw_count_and_position() {
+    let counter_value = CntCount::Cnt2Bit24(-5, 42);
+    assert_eq!(counter_value.raw_count(0), Some(RawCount::new(-5)));
+    assert_eq!(counter_value.raw_count(1), Some(RawCount::new(42)));
+    assert_eq!(counter_value.raw_count(2), None);
+
+    // To get a position from a raw count, the value must be taken out explicitly.
+    let raw = counter_value.raw_count(1).unwrap();
+    let position = Position::new(raw.value());
+    assert_eq!(position.value(), 42);
+}
+
+/// Access the counter values by their channel index with a descriptive error.
+#[test]
+fn test_get_by_index() {
+    let counts = CntCount::Cnt2Bit24(42, -7);
+
+    // Counter 0 is always configured.
+    assert_eq!(counts.get(0), Ok(42));
+
+    // Counter 2 exists on the iC-MD, but is not configured here.
+    assert_eq!(counts.get(2), Err(CountError::NotConfigured(2)));
+
+    // There is no counter 5.
+    assert_eq!(counts.get(5), Err(CountError::OutOfRange(5)));
+}
+
+/// Get the value of counter 0 without unwrapping it, as counter 0 is always configured.
+#[test]
+fn test_cnt0_infallible() {
+    let counts = [
+        CntCount::Cnt1Bit24(-3),
+        CntCount::Cnt2Bit24(42, -7),
+        CntCount::Cnt1Bit48(-140_737_488_355_328),
+        CntCount::Cnt1Bit16(12),
+        CntCount::Cnt1Bit32(-2_147_483_648),
+        CntCount::Cnt2Bit32Bit16(2_147_483_647, 5),
+        CntCount::Cnt2Bit16(-1, 1),
+        CntCount::Cnt3Bit16(7, 8, 9),
+    ];
+    for count in counts {
+        assert_eq!(Some(count.cnt0()), count.get_cnt0(), "{count:?}");
+    }
+}
@@ -0,0 +1,209 @@
+//! This file checks that the counter values of all configurations are decoded with the correct
+//! bit depth, i.e., that the smallest and largest values of each counter are sign extended
+//! correctly.
+
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+use ic_md::{CntCfg, CntRegister, CntSetup, IcMd};
+
+/// Encode the given counter values (value, bit depth) in channel order into the bytes that the
+/// iC-MD sends, including the NERR and NWARN bits (both high).
+fn encode(counters: &[(i64, u32)], len: usize) -> Vec<u8> {
+    let mut raw: u64 = 0b11;
+    let mut shift = 2;
+    for (value, bits) in counters {
+        raw |= (*value as u64 & ((1 << bits) - 1)) << shift;
+        shift += bits;
+    }
+    // Left align the two status bits at bit 6 and 7 of the last byte.
+    raw <<= 6;
+    raw.to_be_bytes()[8 - len..].to_vec()
+}
+
+/// Read the given counter values with the given configuration and return the decoded ones.
+/// The values are read with and without framing verification, which must decode them the same
+/// way.
+fn read_back(config: CntCfg, counters: &[(i64, u32)]) -> [Option<i64>; 3] {
+    let read = [
+        Transaction::transaction_start(),
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(encode(counters, config.read_len())),
+        Transaction::transaction_end(),
+    ];
+    let expectations: Vec<_> = read.iter().cycle().take(2 * read.len()).cloned().collect();
+    let mut spi_device = Mock::new(&expectations);
+    let mut icmd = IcMd::new(&mut spi_device);
+    icmd.set_counter_config(config);
+    let counter_value = icmd.read_counter().unwrap();
+    assert!(icmd.get_device_status().is_ok());
+    icmd.set_verify_framing(true);
+    assert_eq!(icmd.read_counter().unwrap(), counter_value);
+    spi_device.done();
+    counter_value.as_array()
+}
+
+/// Check the smallest, the largest, and a negative value for all counters of a configuration.
+fn check_config(config: CntCfg, bits: &[u32]) {
+    let picks: [fn(u32) -> i64; 3] = [|b| -(1 << (b - 1)), |b| (1 << (b - 1)) - 1, |_| -1];
+    for pick in picks {
+        let counters: Vec<(i64, u32)> = bits.iter().map(|b| (pick(*b), *b)).collect();
+        let mut expected = [None; 3];
+        for (i, (value, _)) in counters.iter().enumerate() {
+            expected[i] = Some(*value);
+        }
+        assert_eq!(read_back(config, &counters), expected, "{config:?}");
+    }
+}
+
+/// Check all single counter configurations.
+#[test]
+fn test_single_counter_widths() {
+    let setup = CntSetup::default();
+    check_config(CntCfg::Cnt1Bit16(setup), &[16]);
+    check_config(CntCfg::Cnt1Bit24(setup), &[24]);
+    check_config(CntCfg::Cnt1Bit32(setup), &[32]);
+    check_config(CntCfg::Cnt1Bit48(setup), &[48]);
+}
+
+/// Check all multi counter configurations.
+#[test]
+fn test_multi_counter_widths() {
+    let setup = CntSetup::default();
+    check_config(CntCfg::Cnt2Bit24(setup, setup), &[24, 24]);
+    check_config(CntCfg::Cnt2Bit32Bit16(setup, setup), &[32, 16]);
+    check_config(CntCfg::Cnt2Bit16(setup, setup), &[16, 16]);
+    check_config(CntCfg::Cnt3Bit16(setup, setup, setup), &[16, 16, 16]);
+}
+
+/// Check the number of bytes that are read for each configuration.
+#[test]
+fn test_read_len() {
+    let setup = CntSetup::default();
+    assert_eq!(CntCfg::Cnt1Bit24(setup).read_len(), 4);
+    assert_eq!(CntCfg::Cnt2Bit24(setup, setup).read_len(), 7);
+    assert_eq!(CntCfg::Cnt1Bit48(setup).read_len(), 7);
+    assert_eq!(CntCfg::Cnt1Bit16(setup).read_len(), 3);
+    assert_eq!(CntCfg::Cnt1Bit32(setup).read_len(), 5);
+    assert_eq!(CntCfg::Cnt2Bit32Bit16(setup, setup).read_len(), 7);
+    assert_eq!(CntCfg::Cnt2Bit16(setup, setup).read_len(), 5);
+    assert_eq!(CntCfg::Cnt3Bit16(setup, setup, setup).read_len(), 7);
+}
+
+/// Check the number of bytes that the driver reads with each configuration.
+#[test]
+fn test_counter_read_len() {
+    let setup = CntSetup::default();
+    let expected = [
+        (CntCfg::Cnt1Bit24(setup), 4),
+        (CntCfg::Cnt2Bit24(setup, setup), 7),
+        (CntCfg::Cnt1Bit48(setup), 7),
+        (CntCfg::Cnt1Bit16(setup), 3),
+        (CntCfg::Cnt1Bit32(setup), 5),
+        (CntCfg::Cnt2Bit32Bit16(setup, setup), 7),
+        (CntCfg::Cnt2Bit16(setup, setup), 5),
+        (CntCfg::Cnt3Bit16(setup, setup, setup), 7),
+    ];
+    let mut spi_device = Mock::<u8>::new(&[]);
+    let mut icmd = IcMd::new(&mut spi_device);
+    for (config, len) in expected {
+        icmd.set_counter_config(config);
+        assert_eq!(icmd.counter_read_len(), len, "{config:?}");
+    }
+    spi_device.done();
+}
+
+/// Check that every configuration is mapped to its own counter read register.
+#[test]
+fn test_count_register_mapping() {
+    let setup = CntSetup::default();
+    let expected = [
+        (CntCfg::Cnt1Bit24(setup), CntRegister::ReadCntCfg0),
+        (CntCfg::Cnt2Bit24(setup, setup), CntRegister::ReadCntCfg1),
+        (CntCfg::Cnt1Bit48(setup), CntRegister::ReadCntCfg2),
+        (CntCfg::Cnt1Bit16(setup), CntRegister::ReadCntCfg3),
+        (CntCfg::Cnt1Bit32(setup), CntRegister::ReadCntCfg4),
+        (
+            CntCfg::Cnt2Bit32Bit16(setup, setup),
+            CntRegister::ReadCntCfg5,
+        ),
+        (CntCfg::Cnt2Bit16(setup, setup), CntRegister::ReadCntCfg6),
+        (
+            CntCfg::Cnt3Bit16(setup, setup, setup),
+            CntRegister::ReadCntCfg7,
+        ),
+    ];
+    for (config, register) in expected {
+        assert_eq!(config.count_register(), register, "{config:?}");
+    }
+
+    // All configurations are covered: The configuration byte holds the configuration in its
+    // three lowest bits, so each of the eight values must map to the expected register.
+    for cfg in 0..8u8 {
+        let register = CntCfg::from(cfg).count_register();
+        assert_eq!(register, expected[cfg as usize].1);
+    }
+}
+
+/// Check the reported widths and number of counters of mixed and single width configurations.
+#[test]
+fn test_channel_widths() {
+    let setup = CntSetup::default();
+
+    let config = CntCfg::Cnt2Bit32Bit16(setup, setup);
+    assert_eq!(config.channel_widths(), [Some(32), Some(16), None]);
+    assert_eq!(config.num_counters(), 2);
+
+    let config = CntCfg::Cnt1Bit48(setup);
+    assert_eq!(config.channel_widths(), [Some(48), None, None]);
+    assert_eq!(config.num_counters(), 1);
+
+    let config = CntCfg::Cnt3Bit16(setup, setup, setup);
+    assert_eq!(config.channel_widths(), [Some(16), Some(16), Some(16)]);
+    assert_eq!(config.num_counters(), 3);
+}
+
+/// Check which channels are configured in each configuration.
+#[test]
+fn test_has_channel() {
+    let setup = CntSetup::default();
+    let expected = [
+        (CntCfg::Cnt1Bit24(setup), [true, false, false]),
+        (CntCfg::Cnt2Bit24(setup, setup), [true, true, false]),
+        (CntCfg::Cnt1Bit48(setup), [true, false, false]),
+        (CntCfg::Cnt1Bit16(setup), [true, false, false]),
+        (CntCfg::Cnt1Bit32(setup), [true, false, false]),
+        (CntCfg::Cnt2Bit32Bit16(setup, setup), [true, true, false]),
+        (CntCfg::Cnt2Bit16(setup, setup), [true, true, false]),
+        (CntCfg::Cnt3Bit16(setup, setup, setup), [true, true, true]),
+    ];
+    for (config, channels) in expected {
+        for (channel, configured) in channels.into_iter().enumerate() {
+            assert_eq!(config.has_channel(channel as u8), configured, "{config:?}");
+        }
+        // There are never more than three channels.
+        assert!(!config.has_channel(3), "{config:?}");
+    }
+}
+
+/// Check the bit depth of the widest counter of each configuration.
+#[test]
+fn test_max_bit_depth() {
+    let setup = CntSetup::default();
+    let expected = [
+        (CntCfg::Cnt1Bit24(setup), 24),
+        (CntCfg::Cnt2Bit24(setup, setup), 24),
+        (CntCfg::Cnt1Bit48(setup), 48),
+        (CntCfg::Cnt1Bit16(setup), 16),
+        (CntCfg::Cnt1Bit32(setup), 32),
+        (CntCfg::Cnt2Bit32Bit16(setup, setup), 32),
+        (CntCfg::Cnt2Bit16(setup, setup), 16),
+        (CntCfg::Cnt3Bit16(setup, setup, setup), 16),
+    ];
+    for (config, bits) in expected {
+        assert_eq!(config.max_bit_depth(), bits, "{config:?}");
+
+        // The widest counter is one of the configured channels.
+        let widest = config.channel_widths().into_iter().flatten().max();
+        assert_eq!(widest, Some(bits), "{config:?}");
+    }
+}
@@ -0,0 +1,58 @@
+//! This file contains compile-time tests for the `defmt` feature.
+//!
+//! Run these tests with `cargo test --features defmt`.
+
+#![cfg(feature = "defmt")]
+
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+use ic_md::{IcMd, dd::DeviceError};
+
+/// Logger that discards all messages, required to link the `defmt` logging calls.
+#[defmt::global_logger]
+struct Logger;
+
+unsafe impl defmt::Logger for Logger {
+    fn acquire() {}
+    unsafe fn flush() {}
+    unsafe fn release() {}
+    unsafe fn write(_bytes: &[u8]) {}
+}
+
+/// Only compiles if the given type implements `defmt::Format`.
+fn assert_format<T: defmt::Format>() {}
+
+/// Ensure that the driver and its errors can be formatted with `defmt`.
+#[test]
+fn test_defmt_format_implemented() {
+    assert_format::<IcMd<Mock<u8>>>();
+    // The errors format their inner SPI error, if that one implements `defmt::Format` as well.
+    assert_format::<DeviceError<u8>>();
+    assert_format::<ic_md::IcMdError<u8>>();
+}
+
+/// Read the full status and log all problems.
+#[test]
+fn test_poll_and_log_status() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::write(0x48 | 0x80),
+        Transaction::read_vec(vec![0xC0, 0x02, 0x00]), // Counter 0 errors, collision
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // The problems are logged and the status is returned for further use.
+    let full_status = icmd.poll_and_log_status().unwrap();
+    assert!(!full_status.is_healthy());
+    assert_eq!(full_status.cnt0_overflow, ic_md::OverflowStatus::Overflow);
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
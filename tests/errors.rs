@@ -0,0 +1,38 @@
+//! This file contains tests that show how to handle the errors of the driver.
+
+use std::error::Error;
+
+use ic_md::{IcMdError, dd::DeviceError};
+
+/// Convert driver errors into boxed errors, e.g., for host applications.
+#[test]
+fn test_device_error_into_boxed_error() {
+    // Any SPI error that implements `Error` can be wrapped, here we use `std::fmt::Error`.
+    let device_error = DeviceError(std::fmt::Error);
+    let boxed: Box<dyn Error> = Box::new(device_error);
+
+    // The source of the error is the inner SPI error.
+    assert!(boxed.source().is_some());
+    assert_eq!(
+        boxed.to_string(),
+        "SPI error: an error occurred when formatting an argument"
+    );
+
+    // The same works for the high-level error, which forwards to the device error.
+    let boxed: Box<dyn Error> = Box::new(IcMdError::from(device_error));
+    assert!(boxed.source().is_some());
+
+    // Errors detected by the driver itself have no source.
+    let boxed: Box<dyn Error> = Box::new(IcMdError::<std::fmt::Error>::InvalidChannel(3));
+    assert!(boxed.source().is_none());
+}
+
+/// Recover the owned SPI error from the device error.
+#[test]
+fn test_device_error_into_inner() {
+    let device_error = DeviceError(embedded_hal::spi::ErrorKind::ModeFault);
+    assert_eq!(
+        device_error.into_inner(),
+        embedded_hal::spi::ErrorKind::ModeFault
+    );
+}
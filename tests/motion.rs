@@ -0,0 +1,193 @@
+//! This file contains tests that show how to use the motion helpers with counter values.
+//!
+//! Most helpers only work on counter values that were already read, so no `SPIDevice` is needed.
+//! Only the `QuadratureCounter` trait reads from the device.
+
+use embedded_hal_mock::eh1::{
+    delay::{CheckedDelay, Transaction as DelayTransaction},
+    spi::{Mock, Transaction},
+};
+
+use ic_md::{CntCount, IcMd, IcMdError, Position, PositionTracker, QuadratureCounter};
+
+/// Estimate a steady velocity from two successive counter reads.
+#[test]
+fn test_velocity_steady() {
+    // Two reads of a 16 bit counter, 1 ms apart.
+    let prev = CntCount::Cnt1Bit16(100);
+    let curr = CntCount::Cnt1Bit16(200);
+
+    // 100 counts in 1 ms are 100'000 counts per second.
+    assert_eq!(ic_md::velocity(&prev, &curr, 1_000, 0), Some(100_000.0));
+
+    // Counter 1 is not present in this configuration.
+    assert!(ic_md::velocity(&prev, &curr, 1_000, 1).is_none());
+}
+
+/// Estimate the velocity when the counter wrapped around between the two reads.
+#[test]
+fn test_velocity_wrap_around() {
+    // The 16 bit counter overflowed from 32'760 to -32'766, i.e., it moved 10 counts forward.
+    let prev = CntCount::Cnt1Bit16(32_760);
+    let curr = CntCount::Cnt1Bit16(-32_766);
+    assert_eq!(ic_md::velocity(&prev, &curr, 1_000, 0), Some(10_000.0));
+
+    // And the same backwards for counter 1 of a two 24 bit counter configuration.
+    let prev = CntCount::Cnt2Bit24(0, -8_388_604);
+    let curr = CntCount::Cnt2Bit24(0, 8_388_604);
+    assert_eq!(ic_md::velocity(&prev, &curr, 2_000, 1), Some(-4_000.0));
+}
+
+/// Convert the counts of a 4096 counts per revolution encoder to degrees and back.
+#[test]
+fn test_count_scaler_degrees() {
+    let scaler = ic_md::CountScaler::per_revolution(4096, 360.0);
+
+    // A quarter revolution is 90 degrees, backwards a half revolution is -180 degrees.
+    assert!((scaler.to_units(1024) - 90.0).abs() < 1e-3);
+    assert!((scaler.to_units(-2048) + 180.0).abs() < 1e-3);
+
+    // And back to counts
+    assert_eq!(scaler.to_counts(90.0), 1024);
+    assert_eq!(scaler.to_counts(-180.0), -2048);
+}
+
+/// Read the position of any quadrature counter, here via a generic function.
+fn position_of<C: QuadratureCounter>(counter: &mut C, channel: u8) -> Option<i64> {
+    counter.read_position(channel)
+}
+
+/// Read positions through the `QuadratureCounter` trait.
+#[test]
+fn test_quadrature_counter_trait() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x04, 0xC0]), // Counter 0 = 4
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup (one 48 bit counter)
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Use the driver through a generic function
+    assert_eq!(position_of(&mut icmd, 0), Some(4));
+
+    // The trait is object safe. Channel 1 is not available in this configuration, so it is not
+    // read from the device.
+    let counter: &mut dyn QuadratureCounter = &mut icmd;
+    assert_eq!(counter.read_position(1), None);
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Track a moving counter across a wrap-around, the counter is not stalled.
+#[test]
+fn test_position_tracker_moving() {
+    let mut tracker = PositionTracker::new(0);
+
+    // The first read initializes the position.
+    assert_eq!(
+        tracker.update(&CntCount::Cnt1Bit16(32_700)),
+        Some(Position::new(32_700))
+    );
+
+    // The 16 bit counter wraps around, the tracked position keeps on increasing.
+    assert_eq!(
+        tracker.update(&CntCount::Cnt1Bit16(-32_736)),
+        Some(Position::new(32_800))
+    );
+    assert_eq!(tracker.last_delta(), Some(100));
+    assert!(!tracker.is_stalled(true));
+}
+
+/// Detect a counter that does not change although the axis is expected to move.
+#[test]
+fn test_position_tracker_stalled() {
+    let mut tracker = PositionTracker::new(1);
+
+    // Not enough reads to decide yet.
+    tracker.update(&CntCount::Cnt2Bit24(0, 500)).unwrap();
+    assert!(!tracker.is_stalled(true));
+
+    // Counter 1 did not change between the two reads.
+    tracker.update(&CntCount::Cnt2Bit24(10, 500)).unwrap();
+    assert_eq!(tracker.last_delta(), Some(0));
+    assert!(tracker.is_stalled(true));
+
+    // If the axis is at rest, an unchanged counter is expected.
+    assert!(!tracker.is_stalled(false));
+
+    // Reads of another configuration are rejected and do not change the tracker.
+    assert_eq!(tracker.update(&CntCount::Cnt1Bit16(0)), None);
+    assert_eq!(tracker.position(), Some(Position::new(500)));
+}
+
+/// SPI transactions of a read of the default 48 bit counter with the given value - testing only.
+fn read_48bit(value: i64) -> [Transaction<u8>; 4] {
+    let mut bytes = value.to_be_bytes()[2..].to_vec();
+    bytes.push(0xC0);
+    [
+        Transaction::transaction_start(),
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(bytes),
+        Transaction::transaction_end(),
+    ]
+}
+
+/// Wait until a decreasing counter passes the target value.
+#[test]
+fn test_wait_for_count_reached() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations: Vec<_> = [300, 200, 90].into_iter().flat_map(read_48bit).collect();
+
+    // Initialize your SPIDevice and your delay provider
+    let mut spi_device = Mock::new(&expectations);
+    let mut delay = CheckedDelay::new(&[
+        DelayTransaction::delay_us(IcMd::<&mut Mock<u8>>::WAIT_POLL_INTERVAL_US),
+        DelayTransaction::delay_us(IcMd::<&mut Mock<u8>>::WAIT_POLL_INTERVAL_US),
+    ]);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // The counter starts above the target, so it counts down and passes the target at 90.
+    assert_eq!(icmd.wait_for_count(0, 100, &mut delay, 10_000), Ok(90));
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+    delay.done();
+}
+
+/// Give up waiting when the counter does not reach the target in time.
+#[test]
+fn test_wait_for_count_timeout() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations: Vec<_> = [0, 10, 20].into_iter().flat_map(read_48bit).collect();
+
+    // Initialize your SPIDevice and your delay provider
+    let mut spi_device = Mock::new(&expectations);
+    let mut delay = CheckedDelay::new(&[
+        DelayTransaction::delay_us(IcMd::<&mut Mock<u8>>::WAIT_POLL_INTERVAL_US),
+        DelayTransaction::delay_us(IcMd::<&mut Mock<u8>>::WAIT_POLL_INTERVAL_US),
+    ]);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // The counter moves too slowly, the last value is reported with the timeout.
+    let timeout = 2 * IcMd::<&mut Mock<u8>>::WAIT_POLL_INTERVAL_US;
+    assert_eq!(
+        icmd.wait_for_count(0, 1_000, &mut delay, timeout),
+        Err(IcMdError::Timeout(20))
+    );
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+    delay.done();
+}
@@ -3,7 +3,7 @@
 
 use embedded_hal_mock::eh1::spi::{Mock, Transaction};
 
-use ic_md::{CntCfg, CntSetup, IcMd};
+use ic_md::{CntCfg, CntSetup, IcMd, InputType, OperationMode};
 
 /// A simple to understand example without any configuration
 ///
@@ -15,7 +15,7 @@ fn test_read_negative_value() {
     let expectations = [
         Transaction::transaction_start(),
         Transaction::write(0x00),
-        Transaction::write(0x02),
+        Transaction::write_vec(vec![0x02, 0x00]),
         Transaction::transaction_end(),
         Transaction::transaction_start(),
         Transaction::write(0x80 | 0x08),
@@ -23,7 +23,7 @@ fn test_read_negative_value() {
         Transaction::transaction_end(),
         Transaction::transaction_start(),
         Transaction::write(0x00),
-        Transaction::write(0x01),
+        Transaction::write_vec(vec![0x01, 0x80]),
         Transaction::transaction_end(),
         Transaction::transaction_start(),
         Transaction::write(0x80 | 0x08),
@@ -47,9 +47,10 @@ fn test_read_negative_value() {
         .expect("Counter 0 should always be set up");
     assert_eq!(cnt_0, -1);
 
-    // Set up two 24 bit counters and initialize the counter again
+    // Set up two 24 bit counters with TTL inputs and initialize the counter again
     let counter_setup = CntCfg::Cnt2Bit24(CntSetup::default(), CntSetup::default());
     icmd.set_counter_config(counter_setup);
+    icmd.set_operation_mode(OperationMode::new().with_input_type(InputType::Ttl));
     icmd.init().unwrap();
 
     let counter_value = icmd.read_counter().unwrap();
@@ -66,3 +67,106 @@ fn test_read_negative_value() {
     // Check that all our expectations are met - testing only
     spi_device.done();
 }
+
+/// Read the same bytes as signed and as unsigned counter values.
+///
+/// If you treat the counter as a ring and handle the wrap-around yourself, the unsigned getters
+/// return the raw bits of the counter without sign extension.
+#[test]
+fn test_read_unsigned_value() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(vec![0x00, 0x05, 0xFF, 0xFD, 0xC0]), // -3, 5
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with two 16 bit counters
+    let mut icmd = IcMd::new(&mut spi_device);
+    icmd.set_counter_config(CntCfg::Cnt2Bit16(CntSetup::default(), CntSetup::default()));
+
+    let counter_value = icmd.read_counter().unwrap();
+
+    // Counter 0 is negative: The unsigned value has the same 16 bits, but is not sign extended.
+    assert_eq!(counter_value.get_cnt0(), Some(-3));
+    assert_eq!(counter_value.get_cnt0_unsigned(), Some(0xFFFD));
+
+    // Counter 1 is positive, so both interpretations agree.
+    assert_eq!(counter_value.get_cnt1(), Some(5));
+    assert_eq!(counter_value.get_cnt1_unsigned(), Some(5));
+
+    // Counter 2 is not configured.
+    assert_eq!(counter_value.get_cnt2_unsigned(), None);
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// In the mixed configuration, counter 0 is the 32 bit counter and counter 1 the 16 bit one.
+///
+/// The counter values are returned in channel order, i.e., counter 0 comes first.
+#[test]
+fn test_read_mixed_width_counter_order() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(vec![0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0xC0]), // 65536, 1
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Set up a 32 bit counter 0 and a 16 bit counter 1
+    let mut icmd = IcMd::new(&mut spi_device);
+    icmd.set_counter_config(CntCfg::Cnt2Bit32Bit16(
+        CntSetup::default(),
+        CntSetup::default(),
+    ));
+
+    // Counter 0 holds a value that only fits into 32 bits.
+    let counter_value = icmd.read_counter().unwrap();
+    assert_eq!(counter_value, ic_md::CntCount::Cnt2Bit32Bit16(65_536, 1));
+    assert_eq!(counter_value.get_cnt0(), Some(65_536));
+    assert_eq!(counter_value.get_cnt1(), Some(1));
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Read a negative 16 bit counter 1 alongside a positive 32 bit counter 0.
+///
+/// In the mixed configuration, counter 0 is 32 bits deep and counter 1 only 16 bits. The
+/// narrower counter must be sign extended on its own bit depth.
+#[test]
+fn test_read_negative_value_mixed_widths() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(vec![0xFF, 0xFB, 0x00, 0x01, 0x86, 0xA0, 0xC0]), // -5, 100'000
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with one 32 bit and one 16 bit counter
+    let mut icmd = IcMd::new(&mut spi_device);
+    icmd.set_counter_config(CntCfg::Cnt2Bit32Bit16(
+        CntSetup::default(),
+        CntSetup::default(),
+    ));
+
+    let counter_value = icmd.read_counter().unwrap();
+    assert_eq!(counter_value.get_cnt0(), Some(100_000));
+    assert_eq!(counter_value.get_cnt1(), Some(-5));
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
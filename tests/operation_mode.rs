@@ -0,0 +1,211 @@
+//! This file contains tests that show how to set up the operation mode of the iC-MD.
+//!
+//! For your application, you will have to provide your own `SPIDevice` interface.
+
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+use ic_md::{
+    CntCfg, CntDirection, CntSetup, CntZSignal, IcMd, IcMdError, InputType, InvalidRegisterValue,
+    OperationMode, ZMode,
+};
+
+/// Convert several operation modes to their register byte and back.
+#[test]
+fn test_operation_mode_round_trip() {
+    let modes = [
+        (OperationMode::new(), 0b0000_0000),
+        (
+            OperationMode::new().with_input_type(InputType::Ttl),
+            0b1000_0000,
+        ),
+        (
+            OperationMode::new()
+                .with_z_clears_cnt0(true)
+                .with_z_clears_cnt1(true),
+            0b0110_0000,
+        ),
+        (OperationMode::new().with_z_config(0b10), 0b0001_0000),
+        (
+            OperationMode::new()
+                .with_input_type(InputType::Ttl)
+                .with_z_clears_cnt1(true)
+                .with_z_config(0b01)
+                .with_tp_config(0b11),
+            0b1100_1110,
+        ),
+    ];
+
+    for (mode, byte) in modes {
+        assert_eq!(u8::from(mode), byte);
+        assert_eq!(OperationMode::try_from(byte), Ok(mode));
+    }
+
+    // Bit 0 is reserved, so a byte with it set cannot be converted.
+    assert_eq!(
+        OperationMode::try_from(0b0000_0001),
+        Err(InvalidRegisterValue(0b0000_0001))
+    );
+}
+
+/// Set up TTL inputs and have the Z signal clear counter 0.
+#[test]
+fn test_init_with_operation_mode() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Counter configuration and operation mode
+        Transaction::write(0x00),
+        Transaction::write_vec(vec![0x02, 0xA0]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Set the operation mode and initialize the counter
+    let mode = OperationMode::new()
+        .with_input_type(InputType::Ttl)
+        .with_z_clears_cnt0(true);
+    icmd.set_operation_mode(mode);
+    icmd.init().unwrap();
+
+    assert_eq!(icmd.get_operation_mode(), mode);
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Initialize a fully specified configuration, which is written in a single transaction.
+#[test]
+fn test_init_fully_specified() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Start at 0x00, auto-increment to 0x01
+        Transaction::write(0x00),
+        Transaction::write_vec(vec![0xC9, 0xF6]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Two 24 bit counters with non-default setups
+    let config = CntCfg::Cnt2Bit24(
+        CntSetup::new(CntDirection::CCW, CntZSignal::Inverted),
+        CntSetup::new(CntDirection::CW, CntZSignal::Inverted),
+    );
+    icmd.set_counter_config(config);
+
+    // Operation mode with all fields set
+    let mode = OperationMode::new()
+        .with_input_type(InputType::Ttl)
+        .with_z_clears_cnt0(true)
+        .with_z_clears_cnt1(true)
+        .with_z_config(0b10)
+        .with_tp_config(0b11);
+    icmd.set_operation_mode(mode);
+
+    // Both cached configurations are flushed to the device
+    icmd.init().unwrap();
+    assert_eq!(icmd.get_counter_config(), config);
+    assert_eq!(icmd.get_operation_mode(), mode);
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Differential inputs are supported with a single counter.
+#[test]
+fn test_init_differential_single_counter() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // One 32 bit counter with differential inputs
+        Transaction::write(0x00),
+        Transaction::write_vec(vec![0x04, 0x00]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter and set up a single 32 bit counter with differential inputs
+    let mut icmd = IcMd::new(&mut spi_device);
+    icmd.set_counter_config(CntCfg::Cnt1Bit32(CntSetup::default()));
+    icmd.set_operation_mode(OperationMode::new().with_input_type(InputType::Differential));
+    icmd.init().unwrap();
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Configurations with more than one counter only support TTL inputs.
+#[test]
+fn test_init_differential_two_counters() {
+    // No SPI transactions are expected, the configuration is rejected before writing it.
+    let mut spi_device = Mock::<u8>::new(&[]);
+
+    // Get a handle to the counter and set up two 24 bit counters with differential inputs
+    let mut icmd = IcMd::new(&mut spi_device);
+    icmd.set_counter_config(CntCfg::Cnt2Bit24(CntSetup::default(), CntSetup::default()));
+    icmd.set_operation_mode(OperationMode::new().with_input_type(InputType::Differential));
+    assert_eq!(icmd.init(), Err(IcMdError::DifferentialNotSupported));
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Set the Z signal mode, which selects the AB state in which the Z signal is evaluated.
+#[test]
+fn test_z_mode_bit_patterns() {
+    let modes = [
+        (ZMode::AHighBHigh, 0b0000_0000),
+        (ZMode::AHighBLow, 0b0000_1000),
+        (ZMode::ALowBHigh, 0b0001_0000),
+        (ZMode::ALowBLow, 0b0001_1000),
+    ];
+    for (z_mode, byte) in modes {
+        let mode = OperationMode::new().with_z_mode(z_mode);
+        assert_eq!(u8::from(mode), byte, "{z_mode:?}");
+        assert_eq!(mode.z_mode(), z_mode);
+
+        // The typed mode and the raw configuration are the same setting.
+        assert_eq!(mode.z_config(), u8::from(z_mode));
+        assert_eq!(OperationMode::try_from(byte).unwrap().z_mode(), z_mode);
+    }
+
+    // The power-on default is the first mode.
+    assert_eq!(OperationMode::new().z_mode(), ZMode::default());
+}
+
+/// The Z signal mode is written to the device by `init()`.
+#[test]
+fn test_init_with_z_mode() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Counter configuration and operation mode
+        Transaction::write(0x00),
+        Transaction::write_vec(vec![0x02, 0x38]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Evaluate the Z signal while A and B are low and clear counter 0 with it
+    let mode = OperationMode::new()
+        .with_z_mode(ZMode::ALowBLow)
+        .with_z_clears_cnt0(true);
+    icmd.set_operation_mode(mode);
+    icmd.init().unwrap();
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
@@ -0,0 +1,36 @@
+//! This file contains tests that show how to read the counters without blocking, e.g., with DMA
+//! or an interrupt driven SPI peripheral.
+//!
+//! No `SPIDevice` is needed: You transmit the bytes that the state machine asks for and feed it
+//! the bytes that you receive.
+
+use ic_md::{CntCfg, CntCount, CntSetup, CounterReadFsm, CounterReadPoll};
+
+/// Step the state machine through a read of two 16 bit counters.
+#[test]
+fn test_counter_read_fsm() {
+    let mut fsm = CounterReadFsm::new(CntCfg::Cnt2Bit16(CntSetup::default(), CntSetup::default()));
+
+    // Bytes that the iC-MD sends - ignore this if you look for the example
+    let rx = [0xFF, 0x00, 0x2A, 0xFF, 0xFE, 0xC0]; // Ignored, counter 1 = 42, counter 0 = -2
+
+    // The address byte and five counter bytes are transferred.
+    assert_eq!(fsm.transfer_len(), rx.len());
+
+    // Transmit the requested byte and feed the received one in your interrupt handler.
+    let mut tx = Vec::new();
+    let mut result = CounterReadPoll::Pending;
+    for byte in rx {
+        assert_eq!(result, CounterReadPoll::Pending);
+        tx.push(fsm.tx_byte());
+        result = fsm.feed(byte);
+    }
+
+    // The address is sent first, followed by dummy bytes.
+    assert_eq!(tx, [0x88, 0x00, 0x00, 0x00, 0x00, 0x00]);
+    assert_eq!(result, CounterReadPoll::Ready(CntCount::Cnt2Bit16(-2, 42)));
+    assert!(fsm.device_status().unwrap().is_ok());
+
+    // The state machine starts over for the next read.
+    assert_eq!(fsm.tx_byte(), 0x88);
+}
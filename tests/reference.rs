@@ -0,0 +1,169 @@
+//! This file contains tests that show how to read the reference counter and the UPD register of
+//! the iC-MD.
+//!
+//! For your application, you will have to provide your own `SPIDevice` interface.
+
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+use ic_md::{IcMd, OverflowStatus, ReferenceAndUpd};
+
+/// Read the UPD register after checking that it was loaded.
+#[test]
+fn test_read_upd_valid() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Read Status0
+        Transaction::write(0x80 | 0x48),
+        Transaction::read(0x04), // UpdVal is set
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Read the UPD register
+        Transaction::write(0x80 | 0x14),
+        Transaction::read_vec(vec![0xFF, 0xFF, 0xFE]), // -2
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Read Status0 again
+        Transaction::write(0x80 | 0x48),
+        Transaction::read(0x00), // UpdVal is not set
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // The UPD register was loaded, so we get its sign extended value back.
+    assert_eq!(icmd.read_upd().unwrap(), Some(-2));
+
+    // If the UPD register is not loaded, `None` is returned and the register is not read.
+    assert_eq!(icmd.read_upd().unwrap(), None);
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Read the reference counter and the UPD register together.
+#[test]
+fn test_read_reference_and_upd_valid() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Read Status0
+        Transaction::write(0x80 | 0x48),
+        Transaction::read(0x0C), // RVal and UpdVal are set
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Read the reference counter
+        Transaction::write(0x80 | 0x10),
+        Transaction::read_vec(vec![0x00, 0x01, 0x00]), // 256
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Read the UPD register
+        Transaction::write(0x80 | 0x14),
+        Transaction::read_vec(vec![0xFF, 0xFF, 0xFE]), // -2
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Both registers are valid, so both values are returned.
+    assert_eq!(
+        icmd.read_reference_and_upd().unwrap(),
+        ReferenceAndUpd {
+            reference: Some(256),
+            upd: Some(-2),
+        }
+    );
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// An overflow of the reference counter invalidates both values.
+#[test]
+fn test_read_reference_and_upd_overflow() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Read Status0
+        Transaction::write(0x80 | 0x48),
+        Transaction::read(0x0E), // RVal, UpdVal, and OvfRef are set
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // No value is valid and neither register is read.
+    let values = icmd.read_reference_and_upd().unwrap();
+    assert_eq!(values.reference, None);
+    assert_eq!(values.upd, None);
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Wait until the reference value was loaded, e.g., during homing.
+#[test]
+fn test_reference_loaded() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Read Status0
+        Transaction::write(0x80 | 0x48),
+        Transaction::read(0x00), // RVal is not set
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Read Status0 again
+        Transaction::write(0x80 | 0x48),
+        Transaction::read(0x08), // RVal is set
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Before the second index pulse, the reference is not loaded.
+    assert!(!icmd.reference_loaded().unwrap());
+
+    // Afterwards, the reference is usable.
+    assert!(icmd.reference_loaded().unwrap());
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Check the reference counter overflow before using the reference, e.g., for interpolation.
+#[test]
+fn test_reference_overflow() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Read Status0
+        Transaction::write(0x80 | 0x48),
+        Transaction::read(0x0A), // OvfRef and RVal are set
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Read Status0 again
+        Transaction::write(0x80 | 0x48),
+        Transaction::read(0x08), // Only RVal is set
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Too many edges between the index pulses: Discard the sample.
+    assert_eq!(icmd.reference_overflow().unwrap(), OverflowStatus::Overflow);
+
+    // The overflow was cleared by the previous read.
+    assert_eq!(icmd.reference_overflow().unwrap(), OverflowStatus::Ok);
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
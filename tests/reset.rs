@@ -0,0 +1,244 @@
+//! This file contains tests that show how to reset the counters of the iC-MD.
+//!
+//! For your application, you will have to provide your own `SPIDevice` interface.
+
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+use ic_md::{IcMd, IcMdSession, InstructionByteConfig, PinStatus};
+
+/// Reset counter 0 and read the counter back to confirm the reset.
+#[test]
+fn test_reset_and_read() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Set ACT0 high
+        Transaction::write(0x30),
+        Transaction::write(0x20),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Reset counter 0, ACT0 stays high
+        Transaction::write(0x30),
+        Transaction::write(0x21),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Read the counter
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Set ACT0 to high, this must be preserved by the reset.
+    icmd.configure_actuator_pins(&PinStatus::High, &PinStatus::Low)
+        .unwrap();
+
+    // Reset counter 0 and read the counter back, which must now be zero.
+    let counter_value = icmd.reset_and_read(true, false, false).unwrap();
+    assert_eq!(counter_value.get_cnt0(), Some(0));
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Reset counter 0 and load the touch probe registers in a single write.
+#[test]
+fn test_write_instruction_combined() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Set ACT1 high
+        Transaction::write(0x30),
+        Transaction::write(0x40),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Reset counter 0, touch probe, ACT0 and ACT1 high
+        Transaction::write(0x30),
+        Transaction::write(0x71),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Set ACT1 to high
+    icmd.configure_actuator_pins(&PinStatus::Low, &PinStatus::High)
+        .unwrap();
+
+    // Start from the current actuator status, so ACT1 stays high, and add the instructions.
+    let instruction = InstructionByteConfig {
+        reset_cnt0: true,
+        touch_probe: true,
+        act0: PinStatus::High,
+        ..InstructionByteConfig::from(icmd.get_actuator_status())
+    };
+    icmd.write_instruction(instruction).unwrap();
+
+    // The cached actuator status is updated with the written levels.
+    let status = icmd.get_actuator_status();
+    assert_eq!(status.act0, PinStatus::High);
+    assert_eq!(status.act1, PinStatus::High);
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Chain several instructions with the builder and write them in a single transaction.
+#[test]
+fn test_instruction_builder() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Set ACT1 high
+        Transaction::write(0x30),
+        Transaction::write(0x40),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Reset counters 0 and 1, touch probe, ACT0 and ACT1 high
+        Transaction::write(0x30),
+        Transaction::write(0x73),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Set ACT1 to high
+    icmd.configure_actuator_pins(&PinStatus::Low, &PinStatus::High)
+        .unwrap();
+
+    // Chain the instructions and commit them. ACT1 is not touched, so it stays high.
+    icmd.instructions()
+        .reset_counter0()
+        .reset_counter1()
+        .touch_probe()
+        .actuator0(PinStatus::High)
+        .commit()
+        .unwrap();
+
+    // The cached actuator status is updated with the written levels.
+    let status = icmd.get_actuator_status();
+    assert_eq!(status.act0, PinStatus::High);
+    assert_eq!(status.act1, PinStatus::High);
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Use a session guard that initializes the device and resets all counters when it ends.
+#[test]
+fn test_session_init_and_reset_on_drop() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Initialize the device
+        Transaction::write(0x00),
+        Transaction::write_vec(vec![0x02, 0x00]),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Read the counter
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x2A, 0xC0]),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Reset all counters on drop
+        Transaction::write(0x30),
+        Transaction::write(0x07),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Initialize the device again
+        Transaction::write(0x00),
+        Transaction::write_vec(vec![0x02, 0x00]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Start a session, which initializes the device, and read the counter.
+    {
+        let mut session = IcMdSession::new(&mut icmd, true).unwrap();
+        let counter_value = session.icmd().read_counter().unwrap();
+        assert_eq!(counter_value.get_cnt0(), Some(42));
+    } // The session ends here and the counters are reset.
+    assert_eq!(icmd.session_error(), None);
+
+    // A session without reset only initializes the device.
+    drop(IcMdSession::new(&mut icmd, false).unwrap());
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Resetting the counters keeps the level of the actuator pins.
+///
+/// The actuator pins are part of the instruction byte as well, so every reset re-applies the
+/// cached actuator status.
+#[test]
+fn test_reset_preserves_actuator_pins() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Set ACT0 high
+        Transaction::write(0x30),
+        Transaction::write(0x20),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Reset counter 0, ACT0 stays high
+        Transaction::write(0x30),
+        Transaction::write(0x21),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Set ACT0 to high and reset counter 0 afterwards.
+    icmd.configure_actuator_pins(&PinStatus::High, &PinStatus::Low)
+        .unwrap();
+    icmd.reset_counters(true, false, false).unwrap();
+
+    // ACT0 is still high.
+    assert_eq!(icmd.get_actuator_status().act0, PinStatus::High);
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Start fresh: Reset all counters, rewrite the configuration, and clear the status latches.
+#[test]
+fn test_full_reset() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Reset all counters
+        Transaction::write(0x30),
+        Transaction::write(0x07),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Rewrite the configuration
+        Transaction::write(0x00),
+        Transaction::write_vec(vec![0x02, 0x00]),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Read and clear the full status
+        Transaction::write(0x80 | 0x48),
+        Transaction::read_vec(vec![0x40, 0x00, 0x00]), // Overflow of counter 0 was latched
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // The cleared status is returned.
+    let cleared = icmd.full_reset().unwrap();
+    assert_eq!(cleared.cnt0_overflow, ic_md::OverflowStatus::Overflow);
+    assert!(!icmd.needs_reconfiguration());
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
@@ -0,0 +1,59 @@
+//! This file contains a test that shows how to use the iC-MD on a shared SPI bus.
+//!
+//! The driver takes an `SpiDevice`, which manages the chip select pin of the iC-MD. To share the
+//! bus with other devices, wrap the bus in one of the `SpiDevice` implementations of
+//! `embedded-hal-bus`, here a `RefCellDevice`. Each device on the bus gets its own chip select
+//! pin. For your application, you will have to provide your own `SpiBus` and pins.
+
+use core::cell::RefCell;
+
+use embedded_hal_bus::spi::RefCellDevice;
+use embedded_hal_mock::eh1::{
+    digital::{Mock as PinMock, State as PinState, Transaction as PinTransaction},
+    spi::{Mock as SpiMock, Transaction},
+};
+
+use ic_md::IcMd;
+
+/// Read two iC-MD counters that share the same SPI bus.
+#[test]
+fn test_two_counters_on_shared_bus() {
+    // SPI bus transactions - ignore this if you look for the example
+    let bus_expectations = [
+        Transaction::write(0x80 | 0x08), // Read counter of the first iC-MD
+        Transaction::read_vec(vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x2A, 0xC0]),
+        Transaction::flush(),
+        Transaction::write(0x80 | 0x08), // Read counter of the second iC-MD
+        Transaction::read_vec(vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x2B, 0xC0]),
+        Transaction::flush(),
+    ];
+
+    // Chip select transactions: set high on creation, then low and high for the transaction
+    let cs_expectations = [
+        PinTransaction::set(PinState::High),
+        PinTransaction::set(PinState::Low),
+        PinTransaction::set(PinState::High),
+    ];
+
+    // Initialize your SPI bus and the chip select pins of the two devices
+    let bus = RefCell::new(SpiMock::new(&bus_expectations));
+    let mut cs0 = PinMock::new(&cs_expectations);
+    let mut cs1 = PinMock::new(&cs_expectations);
+
+    // Create one `SpiDevice` per iC-MD on the shared bus
+    let spi0 = RefCellDevice::new_no_delay(&bus, cs0.clone()).unwrap();
+    let spi1 = RefCellDevice::new_no_delay(&bus, cs1.clone()).unwrap();
+
+    // Get a handle to each counter with the default setup
+    let mut icmd0 = IcMd::new(spi0);
+    let mut icmd1 = IcMd::new(spi1);
+
+    // Read out both counters, the bus is only borrowed for the duration of each transaction
+    assert_eq!(icmd0.read_counter().unwrap().get_cnt0(), Some(42));
+    assert_eq!(icmd1.read_counter().unwrap().get_cnt0(), Some(43));
+
+    // Check that all our expectations are met - testing only
+    bus.borrow_mut().done();
+    cs0.done();
+    cs1.done();
+}
@@ -5,6 +5,9 @@
 //! tests should show you how to simple read the device. These tests will be extensively commented
 //! for the user to better understand the handling.
 
+use core::convert::Infallible;
+
+use embedded_hal::spi::{ErrorType, Operation, SpiDevice};
 use embedded_hal_mock::eh1::spi::{Mock, Transaction};
 
 use ic_md::IcMd;
@@ -19,7 +22,7 @@ fn test_default_icmd_and_counter_read() {
     let expectations = [
         Transaction::transaction_start(),
         Transaction::write(0x00),
-        Transaction::write(0x02),
+        Transaction::write_vec(vec![0x02, 0x00]),
         Transaction::transaction_end(),
         Transaction::transaction_start(),
         Transaction::write(0x80 | 0x08),
@@ -54,3 +57,500 @@ fn test_default_icmd_and_counter_read() {
     // Check that all our expectations are met - testing only
     spi_device.done();
 }
+
+/// Release the SPI device after using the counter.
+///
+/// If you need the SPI device back, e.g., to talk to another peripheral, you can release it from
+/// the driver. This consumes the driver.
+#[test]
+fn test_release_spi_device() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::write(0x00),
+        Transaction::write_vec(vec![0x02, 0x00]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter, this time by moving the SPI device into the driver.
+    let mut icmd = IcMd::new(spi_device);
+
+    // Initialize the counter
+    icmd.init().unwrap();
+
+    // Release the SPI device again, the driver is consumed.
+    let mut spi_device = icmd.release();
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Retry reading the counter if a communication collision occured.
+///
+/// On noisy buses, a communication collision can occur. The driver then retries the read up to
+/// the given number of attempts.
+#[test]
+fn test_read_counter_retry_on_collision() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // First read with NWARN low
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x2A, 0x80]),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Status1 reports a collision
+        Transaction::write(0x80 | 0x49),
+        Transaction::read(0x02),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Second read succeeds
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x2B, 0xC0]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Read the counter with up to three attempts, the second one succeeds.
+    let counter_value = icmd.read_counter_retry(3).unwrap();
+    assert_eq!(counter_value.get_cnt0(), Some(43));
+    assert!(icmd.get_device_status().is_ok());
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Read the counter with a different configuration than the cached one.
+#[test]
+fn test_read_counter_as() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Read as one 24 bit counter
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(vec![0x00, 0x00, 0x2A, 0xC0]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup (one 48 bit counter)
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Probe the counter assuming the power-on configuration of the device.
+    let counter_value = icmd
+        .read_counter_as(IcMd::<&mut Mock<u8>>::POWER_ON_CONFIG)
+        .unwrap();
+    assert_eq!(counter_value, ic_md::CntCount::Cnt1Bit24(42));
+
+    // The cached configuration is not changed.
+    assert_eq!(
+        icmd.get_counter_config(),
+        IcMd::<&mut Mock<u8>>::DEFAULT_CONFIG
+    );
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// SPI device that can be created in a const context and does nothing - testing only.
+struct ConstSpi;
+
+impl ErrorType for ConstSpi {
+    type Error = Infallible;
+}
+
+impl SpiDevice for ConstSpi {
+    fn transaction(&mut self, _operations: &mut [Operation<'_, u8>]) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+/// The driver can be created in a const context, e.g., to place it in a `static`.
+const ICMD: IcMd<ConstSpi> = IcMd::new(ConstSpi);
+
+/// Use a driver that was created in a const context.
+#[test]
+fn test_const_new() {
+    let icmd = ICMD;
+    assert_eq!(icmd.get_counter_config(), IcMd::<ConstSpi>::DEFAULT_CONFIG);
+    assert!(icmd.get_device_status().is_ok());
+    assert!(!icmd.needs_reconfiguration());
+}
+
+/// Measure the time of several counter reads with a free running timer.
+#[test]
+fn test_benchmark_reads() {
+    // SPI transactions - ignore this if you look for the example
+    let read = [
+        Transaction::transaction_start(),
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x2A, 0xC0]),
+        Transaction::transaction_end(),
+    ];
+    let expectations: Vec<_> = read.iter().cycle().take(3 * read.len()).cloned().collect();
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Mock a timer that wraps around during the benchmark - use your hardware timer instead.
+    let mut timer = u32::MAX - 5;
+    let now = || {
+        timer = timer.wrapping_add(10);
+        timer
+    };
+
+    // Three reads take 10 ticks according to our mocked timer.
+    assert_eq!(icmd.benchmark_reads(3, now).unwrap(), 10);
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Verify the framing of counter reads, e.g., during bring-up.
+#[test]
+fn test_verify_framing() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Well-formed read
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x2A, 0xC0]),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Malformed read, the bytes arrive in reverse order
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(vec![0xC0, 0x2A, 0x00, 0x00, 0x00, 0x00, 0x00]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup and verify the framing of reads
+    let mut icmd = IcMd::new(&mut spi_device);
+    icmd.set_verify_framing(true);
+
+    // The well-formed read is decoded as usual.
+    let counter_value = icmd.read_counter().unwrap();
+    assert_eq!(counter_value.get_cnt0(), Some(42));
+    assert!(icmd.get_device_status().is_ok());
+
+    // In the malformed read, NERR and NWARN are both low, so a framing error is returned.
+    assert_eq!(icmd.read_counter(), Err(ic_md::IcMdError::Framing(0x00)));
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Read the counter together with the raw bytes that were received.
+#[test]
+fn test_read_counter_raw() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x2A, 0x80]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Both the decoded value and the raw bytes are returned, e.g., to log them.
+    let (counter_value, frame) = icmd.read_counter_raw().unwrap();
+    assert_eq!(counter_value.get_cnt0(), Some(42));
+    assert_eq!(frame.as_bytes(), [0x00, 0x00, 0x00, 0x00, 0x00, 0x2A, 0x80]);
+
+    // NWARN is low in the raw bytes, so the device status reports a warning.
+    assert_eq!(
+        icmd.get_device_status().get_warning(),
+        ic_md::WarningStatus::Warning
+    );
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Detect a floating bus, which returns all bytes as 0xFF.
+#[test]
+fn test_detect_bus_fault() {
+    // SPI transactions - ignore this if you look for the example
+    let read = [
+        Transaction::transaction_start(),
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(vec![0xFF; 7]), // MISO floats high
+        Transaction::transaction_end(),
+    ];
+    let expectations: Vec<_> = read.iter().cycle().take(2 * read.len()).cloned().collect();
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Without bus fault detection, the floating bus cannot be told apart from a counter at -1.
+    let counter_value = icmd.read_counter().unwrap();
+    assert_eq!(counter_value.get_cnt0(), Some(-1));
+
+    // With bus fault detection, it is reported as a probable disconnect.
+    icmd.set_detect_bus_fault(true);
+    assert_eq!(icmd.read_counter(), Err(ic_md::IcMdError::BusFault));
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Read specific counter read registers, independent of the configuration.
+#[test]
+fn test_read_cfg_register() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // ReadCntCfg0: one 24 bit counter
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(vec![0xFF, 0xFF, 0xD6, 0xC0]),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // ReadCntCfg6: two 16 bit counters
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(vec![0xFF, 0xFE, 0x00, 0x05, 0xC0]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup (one 48 bit counter)
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Read the registers by their index, the values are sign extended.
+    assert_eq!(
+        icmd.read_cfg_register(0).unwrap(),
+        ic_md::CntCount::Cnt1Bit24(-42)
+    );
+    assert_eq!(
+        icmd.read_cfg_register(6).unwrap(),
+        ic_md::CntCount::Cnt2Bit16(5, -2)
+    );
+
+    // There are only eight registers, invalid indices do not access the device.
+    assert_eq!(
+        icmd.read_cfg_register(8),
+        Err(ic_md::IcMdError::InvalidCntRegister(8))
+    );
+
+    // The cached configuration is not changed.
+    assert_eq!(
+        icmd.get_counter_config(),
+        IcMd::<&mut Mock<u8>>::DEFAULT_CONFIG
+    );
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Decode the same counter value with an SPI bridge that presents the bytes LSB-first.
+///
+/// The iC-MD itself is big-endian. Only override the byte order if your hardware path reorders
+/// the bytes.
+#[test]
+fn test_byte_order_override() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Bytes in the order sent by the iC-MD
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(vec![0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xD6, 0xC0]),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // The same bytes in reversed order
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(vec![0xC0, 0xD6, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Read the counter with the byte order of the device.
+    let counter_value = icmd.read_counter().unwrap();
+    assert_eq!(counter_value.get_cnt0(), Some(-42));
+
+    // Flip the byte order and read the same value again.
+    icmd.set_byte_order_override(ic_md::dd::ByteOrderOverride::LittleEndian);
+    assert_eq!(icmd.read_counter().unwrap(), counter_value);
+    assert!(icmd.get_device_status().is_ok());
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Read the counter values directly into a buffer on the stack.
+#[test]
+fn test_read_counter_into() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Two 16 bit counters: -1 and 42
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(vec![0x00, 0x2A, 0xFF, 0xFF, 0xC0]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with two 16 bit counters
+    let mut icmd = IcMd::new(&mut spi_device);
+    icmd.set_counter_config(ic_md::CntCfg::Cnt2Bit16(
+        ic_md::CntSetup::default(),
+        ic_md::CntSetup::default(),
+    ));
+
+    // Two values are written, the third entry is left untouched.
+    let mut buf = [7; 3];
+    assert_eq!(icmd.read_counter_into(&mut buf).unwrap(), 2);
+    assert_eq!(buf, [-1, 42, 7]);
+    assert!(icmd.get_device_status().is_ok());
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Transform the counter values right when reading them.
+#[test]
+fn test_read_counter_map() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Two 16 bit counters: -1 and 42
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(vec![0x00, 0x2A, 0xFF, 0xFF, 0xC0]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with two 16 bit counters
+    let mut icmd = IcMd::new(&mut spi_device);
+    icmd.set_counter_config(ic_md::CntCfg::Cnt2Bit16(
+        ic_md::CntSetup::default(),
+        ic_md::CntSetup::default(),
+    ));
+
+    // Sum up the values of all configured counters.
+    let sum = icmd
+        .read_counter_map(|value| value.as_array().into_iter().flatten().sum::<i64>())
+        .unwrap();
+    assert_eq!(sum, 41);
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Dump the counter data decoded with every counter configuration during bring-up.
+#[test]
+fn test_read_all_layouts() {
+    // SPI transactions - ignore this if you look for the example
+    // The counter stands still at -42, each register is read with its own length.
+    let mut expectations = Vec::new();
+    for len in [4, 7, 7, 3, 5, 7, 5, 7] {
+        let mut data = vec![0xFF; len - 2];
+        data.extend([0xD6, 0xC0]);
+        expectations.extend([
+            Transaction::transaction_start(),
+            Transaction::write(0x80 | 0x08),
+            Transaction::read_vec(data),
+            Transaction::transaction_end(),
+        ]);
+    }
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup (one 48 bit counter)
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Read all layouts, they are indexed by the configuration bits.
+    let layouts = icmd.read_all_layouts().unwrap();
+    assert_eq!(layouts[0], ic_md::CntCount::Cnt1Bit24(-42));
+    assert_eq!(layouts[1], ic_md::CntCount::Cnt2Bit24(-42, -1));
+
+    // The cached configuration is not changed.
+    assert_eq!(
+        icmd.get_counter_config(),
+        IcMd::<&mut Mock<u8>>::DEFAULT_CONFIG
+    );
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Label two counters with an ID, such that they can be distinguished in log output.
+#[test]
+fn test_device_id() {
+    // No SPI transactions are expected, the ID is only stored in the driver.
+    let mut spi_device0 = Mock::<u8>::new(&[]);
+    let mut spi_device1 = Mock::<u8>::new(&[]);
+
+    // Get handles to two counters with different IDs
+    let icmd0 = IcMd::new(&mut spi_device0).with_id(0);
+    let icmd1 = IcMd::new(&mut spi_device1).with_id(1);
+    assert_eq!(icmd0.id(), Some(0));
+    assert_eq!(icmd1.id(), Some(1));
+
+    // The ID shows up in the debug output.
+    assert!(format!("{icmd0:?}").contains("id: Some(0)"));
+    assert!(format!("{icmd1:?}").contains("id: Some(1)"));
+
+    // Without an ID, none is reported.
+    let mut spi_device = Mock::<u8>::new(&[]);
+    let icmd = IcMd::new(&mut spi_device);
+    assert_eq!(icmd.id(), None);
+    assert!(format!("{icmd:?}").contains("id: None"));
+
+    // Check that all our expectations are met - testing only
+    spi_device0.done();
+    spi_device1.done();
+    spi_device.done();
+}
+
+/// Number the counter reads, e.g., to detect dropped samples in a log.
+#[test]
+fn test_read_counter_seq() {
+    // SPI transactions - ignore this if you look for the example
+    let read = |value: u8| {
+        [
+            Transaction::transaction_start(),
+            Transaction::write(0x80 | 0x08),
+            Transaction::read_vec(vec![0x00, 0x00, 0x00, 0x00, 0x00, value, 0xC0]),
+            Transaction::transaction_end(),
+        ]
+    };
+    let expectations: Vec<_> = [read(1), read(2), read(3)].concat();
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // The sequence number starts at zero and increments with every read.
+    for expected in 0..3 {
+        let (sequence, counter_value) = icmd.read_counter_seq().unwrap();
+        assert_eq!(sequence, expected);
+        assert_eq!(counter_value.cnt0(), expected as i64 + 1);
+    }
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
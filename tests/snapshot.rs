@@ -0,0 +1,202 @@
+//! This file contains tests that show how to capture the configuration of the iC-MD and how to
+//! restore it later on.
+//!
+//! For your application, you will have to provide your own `SPIDevice` interface.
+
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+use ic_md::{
+    CntCfg, CntDirection, CntSetup, CntZSignal, ConfigSnapshot, IcMd, IcMdError, InputType,
+    OperationMode,
+};
+
+/// Capture the configuration of a device and restore it again.
+#[test]
+fn test_snapshot_round_trip() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Read 0x00 and 0x01
+        Transaction::write(0x80),
+        Transaction::read_vec(vec![0xC9, 0xF6]),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Write 0x00 and 0x01
+        Transaction::write(0x00),
+        Transaction::write_vec(vec![0xC9, 0xF6]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Capture the configuration of the device, the cached configuration stays the default one.
+    let snapshot = icmd.snapshot_config().unwrap();
+    assert_eq!(
+        snapshot,
+        ConfigSnapshot {
+            counter_config: 0xC9,
+            operation_mode: 0xF6,
+        }
+    );
+    assert_eq!(
+        icmd.get_counter_config(),
+        IcMd::<&mut Mock<u8>>::DEFAULT_CONFIG
+    );
+
+    // Restore the snapshot, which also updates the cached configuration.
+    icmd.restore_config(&snapshot).unwrap();
+    assert_eq!(
+        icmd.get_counter_config(),
+        CntCfg::Cnt2Bit24(
+            CntSetup::new(CntDirection::CCW, CntZSignal::Inverted),
+            CntSetup::new(CntDirection::CW, CntZSignal::Inverted),
+        )
+    );
+    assert_eq!(
+        icmd.get_operation_mode(),
+        OperationMode::new()
+            .with_input_type(InputType::Ttl)
+            .with_z_clears_cnt0(true)
+            .with_z_clears_cnt1(true)
+            .with_z_config(0b10)
+            .with_tp_config(0b11)
+    );
+
+    // A snapshot with an invalid operation mode is not written to the device.
+    let invalid = ConfigSnapshot {
+        counter_config: 0x02,
+        operation_mode: 0x01,
+    };
+    assert_eq!(
+        icmd.restore_config(&invalid),
+        Err(IcMdError::InvalidRegisterValue(0x01))
+    );
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Convert counter configurations to their register byte and back.
+#[test]
+fn test_counter_config_byte_round_trip() {
+    let ccw = CntSetup::new(CntDirection::CCW, CntZSignal::Normal);
+    let inverted = CntSetup::new(CntDirection::CW, CntZSignal::Inverted);
+    let configs = [
+        CntCfg::Cnt1Bit24(ccw),
+        CntCfg::Cnt2Bit24(ccw, inverted),
+        CntCfg::Cnt1Bit48(inverted),
+        CntCfg::Cnt1Bit16(ccw),
+        CntCfg::Cnt1Bit32(inverted),
+        CntCfg::Cnt2Bit32Bit16(inverted, ccw),
+        CntCfg::Cnt2Bit16(ccw, ccw),
+        CntCfg::Cnt3Bit16(ccw, CntSetup::default(), ccw),
+    ];
+    for config in configs {
+        assert_eq!(CntCfg::from(u8::from(config)), config);
+    }
+}
+
+/// Dump all readable registers, e.g., for a register inspection tool.
+#[test]
+fn test_dump_registers() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Read 0x00 and 0x01
+        Transaction::write(0x80),
+        Transaction::read_vec(vec![0x02, 0x00]),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Read the reference counter
+        Transaction::write(0x80 | 0x10),
+        Transaction::read_vec(vec![0x01, 0x02, 0x03]),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Read all status registers
+        Transaction::write(0x80 | 0x48),
+        Transaction::read_vec(vec![0x08, 0x00, 0x01]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    let dump = icmd.dump_registers().unwrap();
+    let addresses: Vec<u8> = dump.iter().map(|(address, _)| *address).collect();
+    assert_eq!(addresses, [0x00, 0x01, 0x10, 0x48, 0x49, 0x4A]);
+    assert_eq!(dump[2], (0x10, 0x010203));
+    assert_eq!(dump[3], (0x48, 0x08));
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Initialize the device and verify that the configuration was latched.
+#[test]
+fn test_init_verified() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Write 0x00 and 0x01
+        Transaction::write(0x00),
+        Transaction::write_vec(vec![0x02, 0x00]),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Read 0x00 and 0x01 back
+        Transaction::write(0x80),
+        Transaction::read_vec(vec![0x02, 0x00]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // The device latched the configuration.
+    icmd.init_verified().unwrap();
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Detect a device that did not latch the configuration, e.g., due to a brown-out during init.
+#[test]
+fn test_init_verified_mismatch() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Write 0x00 and 0x01
+        Transaction::write(0x00),
+        Transaction::write_vec(vec![0x02, 0x00]),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Read back the power-on configuration
+        Transaction::write(0x80),
+        Transaction::read_vec(vec![0x00, 0x00]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // The mismatch is reported with both configurations.
+    assert_eq!(
+        icmd.init_verified(),
+        Err(IcMdError::ConfigMismatch {
+            expected: ConfigSnapshot {
+                counter_config: 0x02,
+                operation_mode: 0x00,
+            },
+            actual: ConfigSnapshot {
+                counter_config: 0x00,
+                operation_mode: 0x00,
+            },
+        })
+    );
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
@@ -0,0 +1,31 @@
+//! This file contains tests that show how to check the SPI clock frequency when creating the
+//! driver.
+//!
+//! For your application, you will have to provide your own `SPIDevice` interface.
+
+use embedded_hal_mock::eh1::spi::Mock;
+
+use ic_md::{IcMd, IcMdError};
+
+/// Create the driver with the maximum allowed SPI clock frequency and with one that is just above.
+#[test]
+fn test_new_checked_boundary() {
+    // No SPI transactions are expected, we only create the driver.
+    let mut spi_device = Mock::new(&[]);
+
+    // The maximum clock frequency is 10 MHz according to the datasheet.
+    assert_eq!(IcMd::<&mut Mock<u8>>::max_spi_hz(), 10_000_000);
+
+    // Creating the driver at exactly the maximum frequency is fine.
+    let max_hz = IcMd::<&mut Mock<u8>>::max_spi_hz();
+    assert!(IcMd::new_checked(&mut spi_device, max_hz).is_ok());
+
+    // One Hz above the maximum returns an error that contains the offending frequency.
+    match IcMd::new_checked(&mut spi_device, max_hz + 1) {
+        Err(IcMdError::SpiFrequencyTooHigh(hz)) => assert_eq!(hz, max_hz + 1),
+        _ => panic!("Creating the driver above the maximum SPI frequency should fail"),
+    }
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
@@ -2,7 +2,10 @@
 //!
 //! For your application, you will have to provide your own `SPIDevice` interface.
 
-use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+use embedded_hal_mock::eh1::{
+    delay::{CheckedDelay, Transaction as DelayTransaction},
+    spi::{Mock, Transaction},
+};
 
 use ic_md::IcMd;
 
@@ -13,23 +16,15 @@ fn test_default_icmd_and_counter_read() {
     let expectations = [
         Transaction::transaction_start(), // Initialization
         Transaction::write(0x00),
-        Transaction::write(0x02),
+        Transaction::write_vec(vec![0x02, 0x00]),
         Transaction::transaction_end(),
         Transaction::transaction_start(), // Read the counter
         Transaction::write(0x80 | 0x08),
         Transaction::read_vec(vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x2A, 0x40]),
         Transaction::transaction_end(),
-        Transaction::transaction_start(), // Get the full device status
+        Transaction::transaction_start(), // Get the full device status in a single transaction
         Transaction::write(0x48 | 0x80),
-        Transaction::read(0x8C), // ABERR0 error
-        Transaction::transaction_end(),
-        Transaction::transaction_start(), // Get the full device status
-        Transaction::write(0x49 | 0x80),
-        Transaction::read(0x00), // no error
-        Transaction::transaction_end(),
-        Transaction::transaction_start(), // Get the full device status
-        Transaction::write(0x4A | 0x80),
-        Transaction::read(0x00), // no error
+        Transaction::read_vec(vec![0x8C, 0x00, 0x00]), // ABERR0 error in Status0, no other errors
         Transaction::transaction_end(),
     ];
 
@@ -108,3 +103,574 @@ fn test_default_icmd_and_counter_read() {
     // Check that all our expectations are met - testing only
     spi_device.done();
 }
+
+/// Extract the status of a single counter from the full device status.
+#[test]
+fn test_counter_status_per_channel() {
+    // Let's assume we read a full device status in which counter 1 overflowed and is at zero.
+    let full_status = ic_md::FullDeviceStatus {
+        cnt1_overflow: ic_md::OverflowStatus::Overflow,
+        cnt1_zero: ic_md::ZeroStatus::Zero,
+        ..Default::default()
+    };
+
+    // Now we can get the status of counter 1 grouped in a single struct.
+    let cnt1_status = full_status
+        .counter_status(1)
+        .expect("Counter 1 status should always be available");
+
+    assert_eq!(cnt1_status.overflow, ic_md::OverflowStatus::Overflow);
+    assert_eq!(cnt1_status.aberr, ic_md::DecodificationStatus::Ok);
+    assert_eq!(cnt1_status.zero, ic_md::ZeroStatus::Zero);
+
+    // Counter 0 is not affected and there is no counter 3.
+    assert_eq!(
+        full_status.counter_status(0),
+        Some(ic_md::CounterStatus::default())
+    );
+    assert!(full_status.counter_status(3).is_none());
+}
+
+/// Check the overall health of the device from the full device status.
+#[test]
+fn test_full_status_is_healthy() {
+    // A clean status is healthy. Zero status and SSI status are ignored for the health check.
+    let clean_status = ic_md::FullDeviceStatus {
+        cnt0_zero: ic_md::ZeroStatus::Zero,
+        ssi_enabled: ic_md::InterfaceStatus::Enabled,
+        ..Default::default()
+    };
+    assert!(clean_status.is_healthy());
+
+    // A single overflow on counter 2 makes the device unhealthy.
+    let overflow_status = ic_md::FullDeviceStatus {
+        cnt2_overflow: ic_md::OverflowStatus::Overflow,
+        ..Default::default()
+    };
+    assert!(!overflow_status.is_healthy());
+}
+
+/// Read the raw status bytes without decoding them.
+#[test]
+fn test_read_raw_status() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // All three status registers in a single transaction
+        Transaction::write(0x48 | 0x80),
+        Transaction::read_vec(vec![0x8C, 0x01, 0x10]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // The raw bytes are returned in the order Status0, Status1, Status2.
+    assert_eq!(icmd.read_raw_status().unwrap(), [0x8C, 0x01, 0x10]);
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Check if the SSI interface is enabled via the SLI pin.
+#[test]
+fn test_is_ssi_enabled() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::write(0x4A | 0x80),
+        Transaction::read(0x01), // SSI enabled
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::write(0x4A | 0x80),
+        Transaction::read(0x00), // SSI disabled
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    assert!(icmd.is_ssi_enabled().unwrap());
+    assert!(!icmd.is_ssi_enabled().unwrap());
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Detect which status fields changed between two full device status reads.
+#[test]
+fn test_status_changes_since() {
+    // The previous status was clean, in the current one counter 0 overflowed.
+    let prev_status = ic_md::FullDeviceStatus::default();
+    let curr_status = ic_md::FullDeviceStatus {
+        cnt0_overflow: ic_md::OverflowStatus::Overflow,
+        ..Default::default()
+    };
+
+    let changes = curr_status.changes_since(&prev_status);
+
+    // Only the overflow of counter 0 changed.
+    assert!(changes.any());
+    assert_eq!(
+        changes,
+        ic_md::StatusChanges {
+            cnt0_overflow: true,
+            ..Default::default()
+        }
+    );
+
+    // Comparing a status with itself reports no changes.
+    assert!(!curr_status.changes_since(&curr_status).any());
+}
+
+/// Poll the level of the touch probe input pin TPI.
+#[test]
+fn test_read_tpi() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::write(0x49 | 0x80),
+        Transaction::read(0x01), // TPI high
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::write(0x49 | 0x80),
+        Transaction::read(0x00), // TPI low
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    assert_eq!(icmd.read_tpi().unwrap(), ic_md::PinStatus::High);
+    assert_eq!(icmd.read_tpi().unwrap(), ic_md::PinStatus::Low);
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Detect a power down reset, even if only one status register reports it.
+#[test]
+fn test_power_down_detected_in_any_register() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::write(0x48 | 0x80),
+        Transaction::read_vec(vec![0x00, 0x10, 0x00]), // Only Status1 reports PDwn
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    let full_status = icmd.get_full_device_status().unwrap();
+    assert!(full_status.power_down_detected());
+    assert_eq!(
+        full_status.power_status,
+        ic_md::UndervoltageStatus::Undervoltage
+    );
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Check for decodification errors on any counter in a tight loop.
+#[test]
+fn test_any_decodification_error() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::write(0x48 | 0x80),
+        Transaction::read_vec(vec![0x00, 0x00, 0x80]), // ABERR2 error
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::write(0x48 | 0x80),
+        Transaction::read_vec(vec![0x7F, 0x7F, 0x7F]), // All other bits set, but no ABERR
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    assert!(icmd.any_decodification_error().unwrap());
+    assert!(!icmd.any_decodification_error().unwrap());
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Latch a power down of the device and clear it again by reinitializing the device.
+#[test]
+fn test_needs_reconfiguration() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::write(0x48 | 0x80),
+        Transaction::read_vec(vec![0x00, 0x10, 0x00]), // Status1 reports PDwn
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::write(0x48 | 0x80),
+        Transaction::read_vec(vec![0x00, 0x00, 0x00]), // No more PDwn
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Reinitialize the device
+        Transaction::write(0x00),
+        Transaction::write_vec(vec![0x02, 0x00]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+    assert!(!icmd.needs_reconfiguration());
+
+    // The power down is latched, even if the next status read does not report it anymore.
+    icmd.get_full_device_status().unwrap();
+    assert!(icmd.needs_reconfiguration());
+    icmd.get_full_device_status().unwrap();
+    assert!(icmd.needs_reconfiguration());
+
+    // Reinitializing the device clears the flag.
+    icmd.init().unwrap();
+    assert!(!icmd.needs_reconfiguration());
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Read the zero status of all counters, e.g., to home an axis.
+#[test]
+fn test_read_zero_events() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::write(0x48 | 0x80),
+        Transaction::read_vec(vec![0x00, 0x20, 0x00]), // Counter 1 reports zero
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    let zero_events = icmd.read_zero_events().unwrap();
+    assert_eq!(
+        zero_events,
+        [
+            ic_md::ZeroStatus::NotZero,
+            ic_md::ZeroStatus::Zero,
+            ic_md::ZeroStatus::NotZero
+        ]
+    );
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Clear an overflow that does not re-assert.
+#[test]
+fn test_clear_overflows_transient() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::write(0x48 | 0x80),
+        Transaction::read_vec(vec![0x40, 0x00, 0x00]), // Counter 0 overflow
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::write(0x48 | 0x80),
+        Transaction::read_vec(vec![0x00, 0x00, 0x00]), // Overflow is gone
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    assert_eq!(
+        icmd.clear_overflows_checked().unwrap(),
+        ic_md::OverflowPersistence::Transient
+    );
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Clear an overflow that re-asserts, and check a status without any overflow.
+#[test]
+fn test_clear_overflows_sustained() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::write(0x48 | 0x80),
+        Transaction::read_vec(vec![0x00, 0x00, 0x40]), // Counter 2 overflow
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::write(0x48 | 0x80),
+        Transaction::read_vec(vec![0x00, 0x00, 0x40]), // Overflow re-asserted
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::write(0x48 | 0x80),
+        Transaction::read_vec(vec![0x00, 0x00, 0x00]), // No overflow, no second read
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    assert_eq!(
+        icmd.clear_overflows_checked().unwrap(),
+        ic_md::OverflowPersistence::Sustained
+    );
+    assert_eq!(
+        icmd.clear_overflows_checked().unwrap(),
+        ic_md::OverflowPersistence::Ok
+    );
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Clear the zero latch of counter 2 only, e.g., to home this axis independently.
+#[test]
+fn test_clear_zero_single_counter() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Only Status2 is read
+        Transaction::write(0x4A | 0x80),
+        Transaction::read(0x20), // Counter 2 reports zero
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    assert_eq!(icmd.clear_zero(2).unwrap(), ic_md::ZeroStatus::Zero);
+
+    // There is no counter 3, so nothing is read.
+    assert_eq!(icmd.clear_zero(3), Err(ic_md::IcMdError::InvalidChannel(3)));
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// The cached device status is updated by a full status read.
+#[test]
+fn test_full_status_updates_device_status() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Read the counter
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x2A, 0x80]), // NWARN is low
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Get the full device status
+        Transaction::write(0x80 | 0x48),
+        Transaction::read_vec(vec![0x00, 0x08, 0x00]), // External error in Status1
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // The counter read reports a warning.
+    icmd.read_counter().unwrap();
+    assert_eq!(
+        icmd.get_device_status().get_warning(),
+        ic_md::WarningStatus::Warning
+    );
+
+    // After the full status read, the cached status reports the external error only.
+    let full_status = icmd.get_full_device_status().unwrap();
+    assert_eq!(icmd.get_device_status(), full_status.to_device_status());
+    assert_eq!(
+        icmd.get_device_status().get_warning(),
+        ic_md::WarningStatus::Ok
+    );
+    assert_eq!(
+        icmd.get_device_status().get_error(),
+        ic_md::ErrorStatus::Error
+    );
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Arm the overflow monitoring after homing, such that later overflows are fresh.
+#[test]
+fn test_arm_overflow_monitoring() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Arm: Read and clear the latched status
+        Transaction::write(0x80 | 0x48),
+        Transaction::read_vec(vec![0x00, 0x40, 0x00]), // Overflow of counter 1 latched
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Later status read
+        Transaction::write(0x80 | 0x48),
+        Transaction::read_vec(vec![0x00, 0x00, 0x00]), // The latch was cleared
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // The overflow that was latched before arming is reported.
+    assert_eq!(
+        icmd.arm_overflow_monitoring().unwrap(),
+        [
+            ic_md::OverflowStatus::Ok,
+            ic_md::OverflowStatus::Overflow,
+            ic_md::OverflowStatus::Ok
+        ]
+    );
+
+    // No new overflow occured since arming.
+    let full_status = icmd.get_full_device_status().unwrap();
+    assert_eq!(full_status.cnt1_overflow, ic_md::OverflowStatus::Ok);
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Debounce the status reads, such that a glitch in a single sample is filtered out.
+#[test]
+fn test_read_status_debounced() {
+    // SPI transactions - ignore this if you look for the example
+    let read_status = |raw: [u8; 3]| {
+        [
+            Transaction::transaction_start(),
+            Transaction::write(0x80 | 0x48),
+            Transaction::read_vec(raw.to_vec()),
+            Transaction::transaction_end(),
+        ]
+    };
+    let mut expectations = Vec::new();
+    expectations.extend(read_status([0x40, 0x00, 0x00])); // Overflow of counter 0
+    expectations.extend(read_status([0x40, 0x08, 0x00])); // Glitch on the external error
+    expectations.extend(read_status([0x40, 0x00, 0x00])); // Overflow of counter 0
+    expectations.extend(read_status([0xA5, 0x5A, 0xC3])); // Single sample
+    expectations.extend(read_status([0xA5, 0x5A, 0xC3])); // Same bytes, read without debouncing
+
+    // Initialize your SPIDevice and the delay provider that spaces the samples
+    let mut spi_device = Mock::new(&expectations);
+    let mut delay = CheckedDelay::new(&[
+        DelayTransaction::delay_us(IcMd::<&mut Mock<u8>>::DEBOUNCE_INTERVAL_US),
+        DelayTransaction::delay_us(IcMd::<&mut Mock<u8>>::DEBOUNCE_INTERVAL_US),
+    ]);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // The overflow is set in all samples, the external error only in one of them.
+    let full_status = icmd.read_status_debounced(3, &mut delay).unwrap();
+    assert_eq!(full_status.cnt0_overflow, ic_md::OverflowStatus::Overflow);
+    assert_eq!(full_status.ext_err_status, ic_md::ErrorStatus::Ok);
+    assert!(icmd.get_device_status().is_ok());
+
+    // A single sample is decoded the same way as the full device status.
+    let debounced = icmd.read_status_debounced(1, &mut delay).unwrap();
+    assert_eq!(debounced, icmd.get_full_device_status().unwrap());
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+    delay.done();
+}
+
+/// Detect a communication collision that is only reported in `Status2`.
+#[test]
+fn test_communication_status() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Read Status1 and Status2
+        Transaction::write(0x80 | 0x49),
+        Transaction::read_vec(vec![0x00, 0x02]), // ComCol only set in Status2
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Read Status1 and Status2 again
+        Transaction::write(0x80 | 0x49),
+        Transaction::read_vec(vec![0x00, 0x00]), // Both collision flags were cleared
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // The collision in Status2 is reported.
+    assert_eq!(
+        icmd.communication_status().unwrap(),
+        ic_md::CommunicationStatus::Collision
+    );
+
+    // Reading the status cleared the collision.
+    assert_eq!(
+        icmd.communication_status().unwrap(),
+        ic_md::CommunicationStatus::Ok
+    );
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Check for fresh overflows in a supervisory loop.
+#[test]
+fn test_overflow_since_last_check() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // First check
+        Transaction::write(0x80 | 0x48),
+        Transaction::read_vec(vec![0x00, 0x00, 0x40]), // Counter 2 overflow
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Second check
+        Transaction::write(0x80 | 0x48),
+        Transaction::read_vec(vec![0x00, 0x00, 0x00]), // No new overflow
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+    assert!(!icmd.overflow_flagged());
+
+    // The first check reports the overflow and clears it.
+    assert!(icmd.overflow_since_last_check().unwrap());
+    assert!(icmd.overflow_flagged());
+
+    // No overflow occured since the first check.
+    assert!(!icmd.overflow_since_last_check().unwrap());
+    assert!(!icmd.overflow_flagged());
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
@@ -0,0 +1,95 @@
+//! This file contains tests that show how to stream counter samples into a buffer.
+//!
+//! For your application, you will have to provide your own `SPIDevice` and delay provider.
+
+use embedded_hal::spi::{ErrorKind, ErrorType, Operation, SpiDevice};
+use embedded_hal_mock::eh1::{
+    delay::{CheckedDelay, NoopDelay, Transaction as DelayTransaction},
+    spi::{Mock, Transaction},
+};
+
+use ic_md::{CntCount, IcMd, IcMdError, PartialReadError, dd::DeviceError};
+
+/// Fill a small buffer with counter samples that are 100 us apart.
+#[test]
+fn test_read_many() {
+    // SPI transactions - ignore this if you look for the example
+    let mut expectations = Vec::new();
+    for value in [1, 2, 3] {
+        expectations.extend([
+            Transaction::transaction_start(),
+            Transaction::write(0x80 | 0x08),
+            Transaction::read_vec(vec![0x00, 0x00, 0x00, 0x00, 0x00, value, 0xC0]),
+            Transaction::transaction_end(),
+        ]);
+    }
+
+    // Initialize your SPIDevice and your delay provider
+    let mut spi_device = Mock::new(&expectations);
+    let mut delay = CheckedDelay::new(&[
+        DelayTransaction::delay_us(100),
+        DelayTransaction::delay_us(100),
+    ]);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Fill the buffer, only two delays are needed between the three reads.
+    let mut buf = [CntCount::Cnt1Bit48(0); 3];
+    assert_eq!(icmd.read_many(&mut buf, &mut delay, 100), Ok(3));
+    assert_eq!(
+        buf,
+        [
+            CntCount::Cnt1Bit48(1),
+            CntCount::Cnt1Bit48(2),
+            CntCount::Cnt1Bit48(3)
+        ]
+    );
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+    delay.done();
+}
+
+/// SPI device that answers a given number of counter reads and fails afterwards - testing only.
+struct FailingSpi {
+    reads_left: usize,
+}
+
+impl ErrorType for FailingSpi {
+    type Error = ErrorKind;
+}
+
+impl SpiDevice for FailingSpi {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), ErrorKind> {
+        if self.reads_left == 0 {
+            return Err(ErrorKind::Other);
+        }
+        self.reads_left -= 1;
+        if let Some(Operation::Read(buf)) = operations.last_mut() {
+            buf.fill(0);
+            buf[buf.len() - 1] = 0xC0; // NERR and NWARN high
+        }
+        Ok(())
+    }
+}
+
+/// Stop streaming on an SPI error and keep the samples collected so far.
+#[test]
+fn test_read_many_error() {
+    // Get a handle to a counter whose SPI device fails on the third read
+    let mut icmd = IcMd::new(FailingSpi { reads_left: 2 });
+
+    let mut buf = [CntCount::Cnt1Bit48(-1); 4];
+    assert_eq!(
+        icmd.read_many(&mut buf, &mut NoopDelay::new(), 100),
+        Err(PartialReadError {
+            read: 2,
+            error: IcMdError::Device(DeviceError(ErrorKind::Other)),
+        })
+    );
+
+    // The first two samples were collected, the rest of the buffer is untouched.
+    assert_eq!(buf[..2], [CntCount::Cnt1Bit48(0); 2]);
+    assert_eq!(buf[2..], [CntCount::Cnt1Bit48(-1); 2]);
+}
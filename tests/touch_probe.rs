@@ -0,0 +1,127 @@
+//! This file contains tests that show how to read the touch probe registers of the iC-MD.
+//!
+//! For your application, you will have to provide your own `SPIDevice` interface.
+
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+use ic_md::{CntCfg, CntSetup, IcMd, PinStatus, TouchProbeStatus};
+
+/// Read the touch probe 1 register after a touch probe event.
+#[test]
+fn test_read_tp1() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Read Status0
+        Transaction::write(0x80 | 0x48),
+        Transaction::read(0x01), // TpVal is set
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Read TP1
+        Transaction::write(0x80 | 0x18),
+        Transaction::read_vec(vec![0x00, 0x00, 0x00, 0x00, 0x01, 0x00]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    let tp1 = icmd.read_tp1().unwrap();
+    assert_eq!(tp1.value, 256);
+    assert_eq!(tp1.status, TouchProbeStatus::Updated);
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Read a negative value from the touch probe 2 register with a 16 bit counter 0.
+#[test]
+fn test_read_tp2() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Read Status0
+        Transaction::write(0x80 | 0x48),
+        Transaction::read(0x00), // TpVal is not set
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Read TP2
+        Transaction::write(0x80 | 0x20),
+        Transaction::read_vec(vec![0x00, 0x00, 0x00, 0x00, 0xFF, 0xFE]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter and set it up with a 16 bit counter 0
+    let mut icmd = IcMd::new(&mut spi_device);
+    icmd.set_counter_config(CntCfg::Cnt1Bit16(CntSetup::default()));
+
+    // The value is sign extended to the 16 bits of counter 0.
+    let tp2 = icmd.read_tp2().unwrap();
+    assert_eq!(tp2.value, -2);
+    assert_eq!(tp2.status, TouchProbeStatus::NotUpdated);
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Read the touch probe status and the TPI pin level together, e.g., to capture edges.
+#[test]
+fn test_touch_probe_state() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Read Status0 and Status1
+        Transaction::write(0x80 | 0x48),
+        Transaction::read_vec(vec![0x01, 0x01]), // TpVal is set, TPI is high
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    let state = icmd.touch_probe_state().unwrap();
+    assert_eq!(state.updated, TouchProbeStatus::Updated);
+    assert_eq!(state.tpi, PinStatus::High);
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
+
+/// Inspect the instruction byte that was last written, e.g., for debugging.
+#[test]
+fn test_last_instruction_byte() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(), // Set ACT0 high
+        Transaction::write(0x30),
+        Transaction::write(0x20),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(), // Touch probe instruction, ACT0 stays high
+        Transaction::write(0x30),
+        Transaction::write(0x30),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter with the default setup
+    let mut icmd = IcMd::new(&mut spi_device);
+
+    // Nothing was written yet.
+    assert_eq!(icmd.last_instruction_byte(), 0x00);
+
+    icmd.configure_actuator_pins(&PinStatus::High, &PinStatus::Low)
+        .unwrap();
+    icmd.touch_probe_instruction().unwrap();
+
+    // The touch probe bit and the ACT0 level were written last.
+    assert_eq!(icmd.last_instruction_byte(), 0x30);
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}
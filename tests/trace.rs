@@ -0,0 +1,60 @@
+//! This file contains tests that show how to trace all register operations of the driver.
+//!
+//! Run these tests with `cargo test --features trace`.
+
+#![cfg(feature = "trace")]
+
+use std::sync::Mutex;
+
+use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+use ic_md::{IcMd, dd::TraceOperation};
+
+/// Recorded trace, the callback cannot capture its environment.
+static TRACE: Mutex<Vec<(TraceOperation, u8, Vec<u8>)>> = Mutex::new(Vec::new());
+
+/// Trace callback that records all register operations.
+fn record(op: TraceOperation, address: u8, data: &[u8]) {
+    TRACE.lock().unwrap().push((op, address, data.to_vec()));
+}
+
+/// Record the trace of an initialization and a counter read.
+#[test]
+fn test_trace_init_and_read() {
+    // SPI transactions - ignore this if you look for the example
+    let expectations = [
+        Transaction::transaction_start(),
+        Transaction::write(0x00),
+        Transaction::write_vec(vec![0x02, 0x00]),
+        Transaction::transaction_end(),
+        Transaction::transaction_start(),
+        Transaction::write(0x80 | 0x08),
+        Transaction::read_vec(vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x2A, 0xC0]),
+        Transaction::transaction_end(),
+    ];
+
+    // Initialize your SPIDevice
+    let mut spi_device = Mock::new(&expectations);
+
+    // Get a handle to the counter and set the trace callback
+    let mut icmd = IcMd::new(&mut spi_device);
+    icmd.set_trace(Some(record));
+
+    icmd.init().unwrap();
+    icmd.read_counter().unwrap();
+
+    assert_eq!(
+        *TRACE.lock().unwrap(),
+        vec![
+            (TraceOperation::Write, 0x00, vec![0x02, 0x00]),
+            (
+                TraceOperation::Read,
+                0x08,
+                vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x2A, 0xC0]
+            ),
+        ]
+    );
+
+    // Check that all our expectations are met - testing only
+    spi_device.done();
+}